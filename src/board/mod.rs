@@ -0,0 +1,385 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+//Smallest and largest supported board sides, i.e. boards from 4x4 up to 25x25
+pub const MIN_SIDE: usize = 4;
+pub const MAX_SIDE: usize = 25;
+
+//The shape of the blocks of a board. A board has as many rows, columns and blocks as a block has
+//cells, e.g. 2 rows of 3 cells make a 6x6 board.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub struct Geometry {
+    pub box_width: usize,
+    pub box_height: usize,
+}
+
+//A parsed sudoku board where 0 represents a blank cell
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Board {
+    geometry: Geometry,
+    cells: Vec<u8>,
+}
+
+//A cell on the board addressed by its zero based row and column
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, Ord, PartialOrd)]
+pub struct Cell {
+    pub row: u8,
+    pub col: u8,
+}
+
+//A row, column or block of the board identified by its zero based number
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, Ord, PartialOrd)]
+pub enum Unit {
+    Row(u8),
+    Col(u8),
+    Block(u8),
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum ParseError {
+    //The number of cells is not the square of a supported board size
+    UnsupportedSize { found: usize },
+    //The board string does not contain one character per cell
+    WrongLength { expected: usize, found: usize },
+    //A character that is neither a value of the board nor a blank marker
+    InvalidCharacter { character: char, cell: Cell },
+    //The same given appears twice in a row, column or block
+    DuplicateGiven { value: u8, unit: Unit, first: Cell, second: Cell },
+}
+
+//Returned by the solvers when no assignment of the blank cells satisfies every constraint
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Unsolvable;
+
+impl Cell {
+    pub fn new(row: u8, col: u8) -> Cell {
+        Cell {
+            row,
+            col,
+        }
+    }
+}
+
+impl Geometry {
+    pub fn new(box_width: usize, box_height: usize) -> Geometry {
+        let geometry = Geometry {
+            box_width,
+            box_height,
+        };
+        assert!(box_width > 1 && box_height > 1 && (MIN_SIDE..=MAX_SIDE).contains(&geometry.side()),
+                "Unsupported geometry!");
+
+        geometry
+    }
+
+    /// The usual geometry of a board with `side` rows: square blocks when `side` is a perfect
+    /// square, otherwise blocks that are as close to square as possible and wider than they are
+    /// tall (2x3 for 6x6, 3x4 for 12x12). Sides without such blocks, e.g. prime ones, are rejected.
+    pub fn for_side(side: usize) -> Option<Geometry> {
+        if !(MIN_SIDE..=MAX_SIDE).contains(&side) {
+            return None;
+        }
+        //The tallest block that is not taller than it is wide
+        let box_height = (2..side).filter(|height| side % height == 0 && height * height <= side).max()?;
+
+        Some(Geometry::new(side / box_height, box_height))
+    }
+
+    //Width and height of the board, which is also the largest value
+    pub fn side(&self) -> usize {
+        self.box_width * self.box_height
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.side() * self.side()
+    }
+
+    pub fn cell_at(&self, idx: usize) -> Cell {
+        Cell::new((idx / self.side()) as u8, (idx % self.side()) as u8)
+    }
+
+    pub fn index_of(&self, cell: Cell) -> usize {
+        cell.row as usize * self.side() + cell.col as usize
+    }
+
+    pub fn block_of(&self, cell: Cell) -> u8 {
+        //Every band of blocks stacked on the same rows holds box_height blocks
+        let (box_width, box_height) = (self.box_width as u8, self.box_height as u8);
+        cell.row / box_height * box_height + cell.col / box_width
+    }
+
+    //Top left cell of a block
+    pub fn block_origin(&self, block: u8) -> Cell {
+        let (box_width, box_height) = (self.box_width as u8, self.box_height as u8);
+        Cell::new(block / box_height * box_height, block % box_height * box_width)
+    }
+
+    //Every row, then every column, then every block of the board
+    pub fn units(&self) -> Vec<Unit> {
+        let side = self.side() as u8;
+        (0..side).map(Unit::Row)
+            .chain((0..side).map(Unit::Col))
+            .chain((0..side).map(Unit::Block))
+            .collect()
+    }
+
+    //The row, column and block containing a cell
+    pub fn cell_units(&self, cell: Cell) -> [Unit; 3] {
+        [Unit::Row(cell.row), Unit::Col(cell.col), Unit::Block(self.block_of(cell))]
+    }
+
+    //Indices of the cells of a unit in row-major order
+    pub fn unit_cells(&self, unit: Unit) -> Vec<usize> {
+        let side = self.side() as u8;
+        match unit {
+            Unit::Row(row) => (0..side).map(|col| self.index_of(Cell::new(row, col))).collect(),
+            Unit::Col(col) => (0..side).map(|row| self.index_of(Cell::new(row, col))).collect(),
+            Unit::Block(block) => {
+                let origin = self.block_origin(block);
+                let mut cells = Vec::with_capacity(self.side());
+                for row in 0..self.box_height as u8 {
+                    for col in 0..self.box_width as u8 {
+                        cells.push(self.index_of(Cell::new(origin.row + row, origin.col + col)));
+                    }
+                }
+                cells
+            },
+        }
+    }
+}
+
+impl Board {
+    /// Parses a board string in row-major order, inferring the geometry from its length (see
+    /// `Geometry::for_side`). Values above 9 are written as letters starting with `A` for 10,
+    /// while `0`, `.` and `_` are blank cells. Boards whose givens already repeat within a row,
+    /// column or block are rejected.
+    pub fn new(board_string: &str) -> Result<Board, ParseError> {
+        let found = board_string.chars().count();
+        match geometry_for_cell_count(found) {
+            Some(geometry) => Board::with_geometry(board_string, geometry),
+            None => Err(ParseError::UnsupportedSize { found }),
+        }
+    }
+
+    /// Parses a board string whose blocks have the given geometry, see `Board::new`.
+    pub fn with_geometry(board_string: &str, geometry: Geometry) -> Result<Board, ParseError> {
+        let side = geometry.side();
+
+        let found = board_string.chars().count();
+        if found != geometry.cell_count() {
+            return Err(ParseError::WrongLength { expected: geometry.cell_count(), found });
+        }
+        let mut cells = Vec::with_capacity(geometry.cell_count());
+
+        for (idx, character) in board_string.chars().enumerate() {
+            match value_from_char(character) {
+                Some(n) if n as usize <= side => {
+                    cells.push(n);
+                },
+                _ => {
+                    if character == '_' || character == '.' {
+                        cells.push(0);
+                    } else {
+                        return Err(ParseError::InvalidCharacter { character, cell: geometry.cell_at(idx) });
+                    }
+                }
+            }
+        }
+
+        let board = Board {
+            geometry,
+            cells
+        };
+        board.check_givens()?;
+
+        Ok(board)
+    }
+
+    /// Builds a board from row-major cell values where 0 is a blank cell. The geometry is
+    /// inferred from the number of cells like `Board::new` does.
+    pub fn from_cells(cells: Vec<u8>) -> Board {
+        let geometry = geometry_for_cell_count(cells.len()).expect("Board is not correct size!");
+        Board::from_cells_with_geometry(cells, geometry)
+    }
+
+    pub fn from_cells_with_geometry(cells: Vec<u8>, geometry: Geometry) -> Board {
+        assert_eq!(cells.len(), geometry.cell_count(), "Board is not correct size!");
+        Board {
+            geometry,
+            cells
+        }
+    }
+
+    pub fn empty(geometry: Geometry) -> Board {
+        Board {
+            geometry,
+            cells: vec![0; geometry.cell_count()]
+        }
+    }
+
+    pub fn cells(&self) -> &[u8] {
+        &self.cells
+    }
+
+    pub fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    pub fn side(&self) -> usize {
+        self.geometry.side()
+    }
+
+    pub fn cell_at(&self, idx: usize) -> Cell {
+        self.geometry.cell_at(idx)
+    }
+
+    pub fn index_of(&self, cell: Cell) -> usize {
+        self.geometry.index_of(cell)
+    }
+
+    pub fn block_of(&self, cell: Cell) -> u8 {
+        self.geometry.block_of(cell)
+    }
+
+    pub fn get(&self, x_pos: u8, y_pos: u8) -> u8 {
+        self.cells[y_pos as usize * self.side() + x_pos as usize]
+    }
+
+    pub fn set(&mut self, x_pos: u8, y_pos: u8, value: u8) {
+        let side = self.side();
+        self.cells[y_pos as usize * side + x_pos as usize] = value;
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.cells.iter().all(|&val| val != 0)
+    }
+
+    //The number of cells holding a given
+    pub fn clue_count(&self) -> usize {
+        self.cells.iter().filter(|&&val| val != 0).count()
+    }
+
+    /// Makes sure that no value is given twice in the same row, column or block.
+    pub fn check_givens(&self) -> Result<(), ParseError> {
+        let side = self.side();
+        //For every unit and value, remember the first cell where the value was seen
+        let mut seen: Vec<Option<Cell>> = vec![None; 3 * side * (side + 1)];
+
+        for (idx, val) in self.cells.iter().enumerate() {
+            if *val == 0 {
+                continue;
+            }
+            let cell = self.cell_at(idx);
+            let units = [Unit::Row(cell.row), Unit::Col(cell.col), Unit::Block(self.block_of(cell))];
+
+            for (kind, unit) in units.iter().enumerate() {
+                let number = match *unit {
+                    Unit::Row(n) | Unit::Col(n) | Unit::Block(n) => n as usize,
+                };
+                let slot = &mut seen[(kind * side + number) * (side + 1) + *val as usize];
+                match *slot {
+                    Some(first) => {
+                        return Err(ParseError::DuplicateGiven { value: *val, unit: *unit, first, second: cell });
+                    },
+                    None => {
+                        *slot = Some(cell);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Finds the geometry of a board with the given number of cells, see `Geometry::for_side`.
+pub fn geometry_for_cell_count(cell_count: usize) -> Option<Geometry> {
+    let side = (MIN_SIDE..=MAX_SIDE).find(|side| side * side == cell_count)?;
+    Geometry::for_side(side)
+}
+
+/// Reads a single value: `1`-`9` followed by `A` (or `a`) for 10, `B` for 11 and so on. `0` is a
+/// blank cell.
+pub fn value_from_char(character: char) -> Option<u8> {
+    character.to_digit(36).map(|n| n as u8)
+}
+
+/// Writes a single value in the format read by `value_from_char`, using `.` for blank cells.
+pub fn value_to_char(value: u8) -> char {
+    if value == 0 {
+        return '.';
+    }
+    ::std::char::from_digit(value as u32, 36).unwrap().to_ascii_uppercase()
+}
+
+impl FromStr for Board {
+    type Err = ParseError;
+
+    fn from_str(board_string: &str) -> Result<Board, ParseError> {
+        Board::new(board_string)
+    }
+}
+
+//Prints the board in the same single line format accepted by `Board::new`
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for val in self.cells.iter() {
+            write!(f, "{}", value_to_char(*val))?;
+        }
+        Ok(())
+    }
+}
+
+//Prints the block shape as width x height
+impl fmt::Display for Geometry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.box_width, self.box_height)
+    }
+}
+
+//Cells are printed 1-based in the usual r1c1 notation
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "r{}c{}", self.row + 1, self.col + 1)
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Unit::Row(n) => write!(f, "row {}", n + 1),
+            Unit::Col(n) => write!(f, "column {}", n + 1),
+            Unit::Block(n) => write!(f, "block {}", n + 1),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::UnsupportedSize { found } => {
+                write!(f, "board has {} cells which does not match any supported size", found)
+            },
+            ParseError::WrongLength { expected, found } => {
+                write!(f, "board has {} cells but {} were expected", found, expected)
+            },
+            ParseError::InvalidCharacter { character, cell } => {
+                write!(f, "invalid character {:?} at {}", character, cell)
+            },
+            ParseError::DuplicateGiven { value, unit, first, second } => {
+                write!(f, "{} is given twice in {} ({} and {})", value, unit, first, second)
+            },
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl fmt::Display for Unsolvable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "board has no solution")
+    }
+}
+
+impl Error for Unsolvable {}
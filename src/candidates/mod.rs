@@ -0,0 +1,282 @@
+//! Pencil marks: the values every open cell of a board may still take.
+//!
+//! ```
+//! use sudoku_solver::Board;
+//! use sudoku_solver::candidates::Candidates;
+//!
+//! let board = Board::new("200005709900070000070001050008200000067000520000008100040300080000060002609800003").unwrap();
+//! let mut candidates = Candidates::from_board(&board);
+//! candidates.remove(1, 3);
+//! assert!(!candidates.has(1, 3));
+//!
+//! //The printed grid parses back into the same pencil marks
+//! let parsed: Candidates = candidates.to_string().parse().unwrap();
+//! assert_eq!(parsed, candidates);
+//! ```
+use std::fmt;
+use std::str::FromStr;
+
+use board::{geometry_for_cell_count, value_from_char, value_to_char, Board, Cell, Geometry, ParseError};
+
+//A single candidate value of a cell
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, Ord, PartialOrd)]
+pub struct Candidate {
+    pub cell: Cell,
+    pub value: u8,
+}
+
+//The pencil marks of a board. Every cell either holds a placed value or the set of values it may
+//still take, stored as a bitset where bit n stands for the value n.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Candidates {
+    geometry: Geometry,
+    values: Vec<u8>,
+    masks: Vec<u32>,
+}
+
+impl Candidate {
+    pub fn new(cell: Cell, value: u8) -> Candidate {
+        Candidate {
+            cell,
+            value,
+        }
+    }
+}
+
+impl Candidates {
+    /// Computes the candidates of every blank cell by removing the values already placed in the
+    /// same row, column or block.
+    pub fn from_board(board: &Board) -> Candidates {
+        let geometry = board.geometry();
+        let mut candidates = Candidates {
+            geometry,
+            values: vec![0; geometry.cell_count()],
+            masks: vec![full_mask(geometry.side()); geometry.cell_count()],
+        };
+
+        for (idx, val) in board.cells().iter().enumerate() {
+            if *val != 0 {
+                candidates.place(idx, *val);
+            }
+        }
+
+        candidates
+    }
+
+    /// Parses a pencil-mark grid like the one printed by `Candidates`: every cell is written as
+    /// its values run together, e.g. `147`, and any characters other than letters, digits and `*`
+    /// separate the cells. A cell with a single value counts as placed unless it is marked as open
+    /// with a trailing `*`, e.g. `4*`, and a lone `*` is an open cell without any candidates. The
+    /// geometry is inferred from the number of cells like `Board::new` does, and placed values
+    /// that repeat within a row, column or block are rejected just the same.
+    pub fn parse(grid: &str) -> Result<Candidates, ParseError> {
+        let found = cell_tokens(grid).len();
+        match geometry_for_cell_count(found) {
+            Some(geometry) => Candidates::parse_with_geometry(grid, geometry),
+            None => Err(ParseError::UnsupportedSize { found }),
+        }
+    }
+
+    /// Parses a pencil-mark grid whose blocks have the given geometry, see `Candidates::parse`.
+    pub fn parse_with_geometry(grid: &str, geometry: Geometry) -> Result<Candidates, ParseError> {
+        let tokens = cell_tokens(grid);
+        if tokens.len() != geometry.cell_count() {
+            return Err(ParseError::WrongLength { expected: geometry.cell_count(), found: tokens.len() });
+        }
+
+        let mut candidates = Candidates {
+            geometry,
+            values: vec![0; geometry.cell_count()],
+            masks: vec![0; geometry.cell_count()],
+        };
+        for (idx, token) in tokens.iter().enumerate() {
+            let is_open = token.ends_with('*');
+            for character in token.trim_end_matches('*').chars() {
+                match value_from_char(character) {
+                    Some(value) if value != 0 && value as usize <= geometry.side() => {
+                        candidates.masks[idx] |= 1 << value;
+                    },
+                    _ => return Err(ParseError::InvalidCharacter { character, cell: geometry.cell_at(idx) }),
+                }
+            }
+            if !is_open && candidates.count(idx) == 1 {
+                candidates.values[idx] = mask_values(candidates.masks[idx])[0];
+                candidates.masks[idx] = 0;
+            }
+        }
+        //Placed values are givens as far as solving goes, so they may not repeat either
+        candidates.to_board().check_givens()?;
+
+        Ok(candidates)
+    }
+
+    pub fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    //The placed value of a cell or 0 if the cell is still open
+    pub fn value(&self, idx: usize) -> u8 {
+        self.values[idx]
+    }
+
+    //Bitset of the remaining candidates of a cell, empty once a value has been placed
+    pub fn mask(&self, idx: usize) -> u32 {
+        self.masks[idx]
+    }
+
+    pub fn has(&self, idx: usize, value: u8) -> bool {
+        self.masks[idx] & (1 << value) != 0
+    }
+
+    pub fn count(&self, idx: usize) -> u32 {
+        self.masks[idx].count_ones()
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.values.iter().all(|&val| val != 0)
+    }
+
+    /// Places a value and removes it from the candidates of every cell sharing a row, column or
+    /// block with it.
+    pub fn place(&mut self, idx: usize, value: u8) {
+        self.values[idx] = value;
+        self.masks[idx] = 0;
+
+        for peer in peers(self.geometry, idx) {
+            self.remove(peer, value);
+        }
+    }
+
+    /// Removes a candidate, returning whether it was still present.
+    pub fn remove(&mut self, idx: usize, value: u8) -> bool {
+        let had_value = self.has(idx, value);
+        self.masks[idx] &= !(1 << value);
+        had_value
+    }
+
+    /// Adds a candidate back to an open cell, returning whether it was missing.
+    pub fn add(&mut self, idx: usize, value: u8) -> bool {
+        assert!(self.values[idx] == 0, "Cannot add a candidate to a placed cell!");
+        let had_value = self.has(idx, value);
+        self.masks[idx] |= 1 << value;
+        !had_value
+    }
+
+    /// Replaces the candidates of an open cell without touching any other cell.
+    pub fn set_mask(&mut self, idx: usize, mask: u32) {
+        assert!(self.values[idx] == 0, "Cannot set the candidates of a placed cell!");
+        self.masks[idx] = mask & full_mask(self.geometry.side());
+    }
+
+    /// The board made of the placed values.
+    pub fn to_board(&self) -> Board {
+        Board::from_cells_with_geometry(self.values.clone(), self.geometry)
+    }
+}
+
+/// Bitset holding every value of a board with `side` values.
+pub fn full_mask(side: usize) -> u32 {
+    ((1u32 << (side + 1)) - 1) & !1
+}
+
+/// The values of a bitset in increasing order.
+pub fn mask_values(mask: u32) -> Vec<u8> {
+    (1..32).filter(|value| mask & (1 << value) != 0).map(|value| value as u8).collect()
+}
+
+//The cells of a pencil-mark grid: runs of letters, digits and open cell markers between any other
+//characters
+fn cell_tokens(grid: &str) -> Vec<&str> {
+    grid.split(|character: char| !character.is_ascii_alphanumeric() && character != '*')
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+//Every other cell in the same row, column or block as idx
+pub fn peers(geometry: Geometry, idx: usize) -> Vec<usize> {
+    let mut peers: Vec<usize> = Vec::with_capacity(3 * geometry.side());
+    for unit in geometry.cell_units(geometry.cell_at(idx)).iter() {
+        for other in geometry.unit_cells(*unit) {
+            if other != idx && !peers.contains(&other) {
+                peers.push(other);
+            }
+        }
+    }
+
+    peers
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}={}", self.cell, self.value)
+    }
+}
+
+impl FromStr for Candidates {
+    type Err = ParseError;
+
+    fn from_str(grid: &str) -> Result<Candidates, ParseError> {
+        Candidates::parse(grid)
+    }
+}
+
+//Prints the usual pencil-mark grid with a line per row, every column as wide as its longest cell
+//and borders around the blocks, e.g.
+//.------------------.------------------.------------------.
+//| 2     1369  1346 | 1346  1348  5    | 7     346   9    |
+//...
+//'------------------'------------------'------------------'
+//Open cells with a single candidate or none at all get a trailing `*` to tell them apart from
+//placed values.
+impl fmt::Display for Candidates {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let side = self.geometry.side();
+        let cells: Vec<String> = (0..self.values.len())
+            .map(|idx| match self.values[idx] {
+                0 if self.masks[idx].count_ones() <= 1 => {
+                    let mut cell: String = mask_values(self.masks[idx]).into_iter().map(value_to_char).collect();
+                    cell.push('*');
+                    cell
+                },
+                0 => mask_values(self.masks[idx]).into_iter().map(value_to_char).collect(),
+                value => value_to_char(value).to_string(),
+            })
+            .collect();
+        let widths: Vec<usize> = (0..side)
+            .map(|col| (0..side).map(|row| cells[row * side + col].len()).max().unwrap_or(0))
+            .collect();
+
+        //The border above the given row, with the corners matching the position on the board
+        let border = |row: usize| -> String {
+            let (outer, inner) = match row {
+                0 => ('.', '.'),
+                _ if row == side => ('\'', '\''),
+                _ => (':', '+'),
+            };
+            let mut line = String::new();
+            for (col, width) in widths.iter().enumerate() {
+                if col % self.geometry.box_width == 0 {
+                    line.push(if col == 0 { outer } else { inner });
+                    line.push('-');
+                }
+                line.push_str(&"-".repeat(width + 1));
+            }
+            line.push(outer);
+            line
+        };
+
+        for row in 0..side {
+            if row % self.geometry.box_height == 0 {
+                writeln!(f, "{}", border(row))?;
+            }
+            for col in 0..side {
+                if col % self.geometry.box_width == 0 {
+                    write!(f, "| ")?;
+                }
+                write!(f, "{:width$} ", cells[row * side + col], width = widths[col])?;
+            }
+            writeln!(f, "|")?;
+        }
+        write!(f, "{}", border(side))
+    }
+}
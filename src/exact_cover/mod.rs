@@ -0,0 +1,804 @@
+//! A generic implementation of Knuth's Algorithm X using dancing links.
+//!
+//! ```
+//! use sudoku_solver::exact_cover::ExactCover;
+//!
+//! //Cover the numbers 1 to 4 exactly once using the given sets
+//! let mut exact_cover = ExactCover::new();
+//! for number in 1..5 {
+//!     exact_cover.add_column(number);
+//! }
+//! exact_cover.add_row("a", &[1, 2]);
+//! exact_cover.add_row("b", &[2, 3]);
+//! exact_cover.add_row("c", &[3, 4]);
+//! exact_cover.add_row("d", &[1]);
+//!
+//! let mut solution = exact_cover.solve().unwrap();
+//! solution.sort();
+//! assert_eq!(solution, vec!["a", "c"]);
+//! ```
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use random::Random;
+
+/// An exact cover problem: choose rows so that every declared column is covered by exactly one of
+/// them. Rows and columns can be any type, the rows of a solution are returned as they were added.
+pub struct ExactCover<R, C> {
+    network: Network<R, C>,
+    column_header_root_id: usize,
+    //Map a column to the corresponding column header id
+    columns: HashMap<C, usize>,
+    //The first field of every row in the order the rows were added
+    rows: Vec<usize>,
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum NodeType<R, C> {
+    //The root links together every uncovered column header
+    Root,
+    //A column header is represented by a column and size value
+    ColumnHeader(C, usize),
+    //A field is represented by its corresponding row and its corresponding column header [id]
+    Field(R, usize)
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Node<R, C> {
+    pub node_type: NodeType<R, C>,
+    pub id: usize,
+    pub left_id: usize,
+    pub right_id: usize,
+    pub up_id: usize,
+    pub down_id: usize
+}
+
+/// How much work a search took: the nodes of the search tree, how deep it went, how often it had
+/// to undo a choice, and how many options it had at each level.
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct SearchStats {
+    pub nodes: usize,
+    pub max_depth: usize,
+    pub backtracks: usize,
+    //Indexed by depth: how many nodes were visited and how many rows they could choose from
+    pub levels: Vec<LevelStats>,
+    //Nodes whose smallest column still had more than one row, so the search had to guess
+    pub guesses: usize,
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub struct LevelStats {
+    pub nodes: usize,
+    pub branches: usize,
+}
+
+pub struct Network<R, C> {
+    network: Vec<RefCell<Node<R, C>>>,
+    current_id: usize,
+}
+
+pub struct HorizontalNodeIterator<'a, R: 'a, C: 'a> {
+    network: &'a Network<R, C>,
+    start_node: usize,
+    current_node: usize,
+    has_started: bool,
+}
+
+pub struct VerticalNodeIterator<'a, R: 'a, C: 'a> {
+    network: &'a Network<R, C>,
+    start_node: usize,
+    current_node: usize,
+    has_started: bool,
+}
+
+impl<R: Clone, C: Clone> Default for Network<R, C> {
+    fn default() -> Network<R, C> {
+        Network::new()
+    }
+}
+
+impl<R: Clone, C: Clone> Network<R, C> {
+    pub fn new() -> Network<R, C> {
+        Network {
+            network: Vec::new(),
+            current_id: 0,
+        }
+    }
+
+    //Makes a node for the network and returns the id for the newly created node
+    pub fn create_node(&mut self, node_type: NodeType<R, C>) -> usize {
+        let node_id = self.current_id;
+        //Remember the column header before the node type is moved into the node
+        let field_header_id = match node_type {
+            NodeType::Field(_, column_header_id) => Some(column_header_id),
+            _ => None,
+        };
+        let node = Node {
+            node_type,
+            id: node_id,
+            left_id: node_id,
+            right_id: node_id,
+            up_id: node_id,
+            down_id: node_id,
+        };
+
+        self.current_id += 1;
+        self.network.push(RefCell::new(node));
+
+        //After adding the node to the network, check if the node is a field and append it under
+        //its corresponding column header
+        if let Some(column_header_id) = field_header_id {
+            self.append_node_vertically(column_header_id, node_id);
+            //Increment the size
+            match self.get_node(column_header_id).borrow_mut().node_type {
+                NodeType::ColumnHeader(_, ref mut size) => {
+                    *size += 1;
+                },
+                _ => {panic!()},
+            }
+        }
+
+        return node_id;
+    }
+
+    pub fn get_node(&self, node_id: usize) -> &RefCell<Node<R, C>> {
+        self.network.get(node_id).unwrap()
+    }
+
+    pub fn get_column_header(&self, node_id: usize) -> &RefCell<Node<R, C>> {
+        match self.get_node(node_id).borrow().node_type {
+            NodeType::Field(_, column_header_id) => {
+                return self.get_node(column_header_id);
+            },
+            _ => {
+                panic!("Node is not a field!");
+            },
+        }
+    }
+
+    pub fn get_column_header_size(&self, header_id: usize) -> usize {
+        match self.get_node(header_id).borrow().node_type {
+            NodeType::ColumnHeader(_, size) => {
+                return size;
+            },
+            _ => {
+                panic!("Node is not a column header")
+            },
+        }
+    }
+
+    //Append node2 to the right of node1
+    pub fn append_node_horizontally(&self, node1_id: usize, node2_id: usize) {
+        //Can't append the same node to itself
+        assert_ne!(node1_id, node2_id);
+
+        let node1 = self.get_node(node1_id);
+        let node2 = self.get_node(node2_id);
+        let node1_right = self.get_node(node1.borrow().right_id);
+
+        node2.borrow_mut().left_id = node1_id;
+        node2.borrow_mut().right_id = node1_right.borrow().id;
+
+        node1.borrow_mut().right_id = node2_id;
+        node1_right.borrow_mut().left_id = node2_id;
+    }
+
+    //Append node2 to the bottom of node1
+    pub fn append_node_vertically(&self, node1_id: usize, node2_id: usize) {
+        //Can't append the same node to itself
+        assert_ne!(node1_id, node2_id);
+
+        let node1 = self.get_node(node1_id);
+        let node2 = self.get_node(node2_id);
+        let node1_down = self.get_node(node1.borrow().down_id);
+
+        node2.borrow_mut().up_id = node1_id;
+        node2.borrow_mut().down_id = node1_down.borrow().id;
+
+        node1.borrow_mut().down_id = node2_id;
+        node1_down.borrow_mut().up_id = node2_id;
+    }
+
+    pub fn horizontal_iter(&self, node_id: usize) -> HorizontalNodeIterator<'_, R, C> {
+        HorizontalNodeIterator {
+            network: self,
+            start_node: node_id,
+            current_node: node_id,
+            has_started: false,
+        }
+    }
+
+    pub fn vertical_iter(&self, node_id: usize) -> VerticalNodeIterator<'_, R, C> {
+        VerticalNodeIterator {
+            network: self,
+            start_node: node_id,
+            current_node: node_id,
+            has_started: false,
+        }
+    }
+
+    pub fn cover_column(&self, column_id: usize) {
+        if let NodeType::Field(_, _) = self.get_node(column_id).borrow().node_type {
+            panic!("Not a column!");
+        }
+        let current_header = self.get_node(column_id);
+
+        let right_id = current_header.borrow().right_id;
+        let left_id = current_header.borrow().left_id;
+
+        let right_header = self.get_node(right_id);
+        let left_header = self.get_node(left_id);
+
+        //Cover the column header
+        right_header.borrow_mut().left_id = left_id;
+        left_header.borrow_mut().right_id = right_id;
+
+        let mut column_iterator = self.vertical_iter(column_id);
+        //Omit the column header from the iteration
+        column_iterator.next();
+        for col_field_id in column_iterator {
+            let mut row_iterator = self.horizontal_iter(col_field_id);
+            //Omit the starting field
+            row_iterator.next();
+            for row_field_id in row_iterator {
+                //Cover up the current field
+                let column_header = self.get_column_header(row_field_id);
+
+                let current_node = self.get_node(row_field_id);
+
+                let above_id = current_node.borrow().up_id;
+                let down_id = current_node.borrow().down_id;
+
+                let above_node = self.get_node(above_id);
+                let down_node = self.get_node(down_id);
+
+                above_node.borrow_mut().down_id = down_id;
+                down_node.borrow_mut().up_id = above_id;
+
+                //Lower the size of the current column header
+                match column_header.borrow_mut().node_type {
+                    NodeType::ColumnHeader(_, ref mut size) => {
+                        *size -= 1;
+                    },
+                    _ => {panic!()},
+                }
+            }
+        }
+    }
+
+    pub fn uncover_column(&self, column_id: usize) {
+        if let NodeType::Field(_, _) = self.get_node(column_id).borrow().node_type {
+            panic!("Not a column!");
+        }
+        let current_header = self.get_node(column_id);
+
+        let right_header = self.get_node(current_header.borrow().right_id);
+        let left_header = self.get_node(current_header.borrow().left_id);
+
+        //Uncover the column header
+        right_header.borrow_mut().left_id = column_id;
+        left_header.borrow_mut().right_id = column_id;
+
+        let mut column_iterator = self.vertical_iter(column_id);
+        //Omit the column header from the iteration
+        column_iterator.next();
+        for col_field_id in column_iterator {
+            let mut row_iterator = self.horizontal_iter(col_field_id);
+            //Omit the starting field
+            row_iterator.next();
+            for row_field_id in row_iterator {
+                //Uncover up the current field
+                let column_header = self.get_column_header(row_field_id);
+
+                let current_node = self.get_node(row_field_id);
+
+                let above_node = self.get_node(current_node.borrow().up_id);
+                let down_node = self.get_node(current_node.borrow().down_id);
+
+                above_node.borrow_mut().down_id = row_field_id;
+                down_node.borrow_mut().up_id = row_field_id;
+
+                //Lower the size of the current column header
+                match column_header.borrow_mut().node_type {
+                    NodeType::ColumnHeader(_, ref mut size) => {
+                        *size += 1;
+                    },
+                    _ => {panic!()},
+                }
+            }
+        }
+    }
+
+    //Covers every other column of the row containing field_id
+    pub fn cover_row(&self, field_id: usize) {
+        let mut row_iter = self.horizontal_iter(field_id);
+        row_iter.next();
+
+        for row_field_id in row_iter {
+            let current_node_header_id = self.get_column_header(row_field_id).borrow().id;
+            self.cover_column(current_node_header_id);
+        }
+    }
+
+    //Undoes cover_row by uncovering the columns from right to left
+    pub fn uncover_row(&self, field_id: usize) {
+        let mut row_field_id = self.get_node(field_id).borrow().left_id;
+
+        while row_field_id != field_id {
+            let current_node_header_id = self.get_column_header(row_field_id).borrow().id;
+            self.uncover_column(current_node_header_id);
+            row_field_id = self.get_node(row_field_id).borrow().left_id;
+        }
+    }
+
+    //Finds the uncovered column header with the smallest size or the root if every column is covered
+    pub fn smallest_column(&self, column_header_root_id: usize) -> usize {
+        let root = self.get_node(column_header_root_id);
+        let mut smallest_id = root.borrow().right_id;
+
+        let mut headers_iter = self.horizontal_iter(column_header_root_id);
+        //Omit the root header
+        headers_iter.next();
+        for header_id in headers_iter {
+            if self.get_column_header_size(header_id) < self.get_column_header_size(smallest_id) {
+                smallest_id = header_id;
+            }
+        }
+
+        return smallest_id;
+    }
+
+    pub fn get_row(&self, field_id: usize) -> R {
+        match self.get_node(field_id).borrow().node_type {
+            NodeType::Field(ref row, _) => {
+                row.clone()
+            },
+            _ => {panic!("Node is not a field!")},
+        }
+    }
+
+    /// Searches for the first exact cover, pushing its rows onto `solution` and recording the
+    /// effort spent in `stats`. The network is left uncovered either way.
+    pub fn solve_exact_cover(&self, column_header_root_id: usize, solution: &mut Vec<R>, stats: &mut SearchStats) -> bool {
+        stats.visit(solution.len());
+
+        //Find column header with smallest size
+        let smallest_id = self.smallest_column(column_header_root_id);
+
+        //Solution has been found! ... Empty matrix
+        if smallest_id == column_header_root_id {
+            return true
+        }
+        //Impossible configuration to solve
+        let size = self.get_column_header_size(smallest_id);
+        stats.branch(solution.len(), size);
+        if size == 0 {
+            return false;
+        }
+
+        //Cover the smallest header
+        self.cover_column(smallest_id);
+
+        let mut col_iter = self.vertical_iter(smallest_id);
+        col_iter.next();
+
+        let mut found = false;
+        for col_field_id in col_iter {
+            //Cover the row
+            self.cover_row(col_field_id);
+
+            //Add current row to the partial solution
+            solution.push(self.get_row(col_field_id));
+            found = self.solve_exact_cover(column_header_root_id, solution, stats);
+
+            //Uncover the row
+            self.uncover_row(col_field_id);
+
+            if found {
+                break;
+            }
+            //If no solution was found, backtrack...
+            solution.pop();
+            stats.backtracks += 1;
+        }
+
+        self.uncover_column(smallest_id);
+
+        return found;
+    }
+
+    /// Searches for an exact cover that contains the row of `field_id`, pushing its rows onto
+    /// `solution` like `solve_exact_cover`. The network is left uncovered either way.
+    pub fn solve_exact_cover_containing(&self, column_header_root_id: usize, field_id: usize, solution: &mut Vec<R>,
+                                        stats: &mut SearchStats) -> bool {
+        let header_id = self.get_column_header(field_id).borrow().id;
+        self.cover_column(header_id);
+        self.cover_row(field_id);
+        solution.push(self.get_row(field_id));
+
+        let found = self.solve_exact_cover(column_header_root_id, solution, stats);
+        if !found {
+            solution.pop();
+        }
+
+        self.uncover_row(field_id);
+        self.uncover_column(header_id);
+
+        return found;
+    }
+
+    /// Counts the exact covers with the same search as `solve_exact_cover`, stopping as soon as
+    /// `limit` have been found.
+    pub fn count_exact_covers(&self, column_header_root_id: usize, limit: usize) -> usize {
+        if limit == 0 {
+            return 0;
+        }
+
+        let smallest_id = self.smallest_column(column_header_root_id);
+        if smallest_id == column_header_root_id {
+            return 1;
+        }
+        if self.get_column_header_size(smallest_id) == 0 {
+            return 0;
+        }
+
+        self.cover_column(smallest_id);
+
+        let mut count = 0;
+        let mut col_iter = self.vertical_iter(smallest_id);
+        col_iter.next();
+
+        for col_field_id in col_iter {
+            self.cover_row(col_field_id);
+            count += self.count_exact_covers(column_header_root_id, limit - count);
+            self.uncover_row(col_field_id);
+
+            if count == limit {
+                break;
+            }
+        }
+
+        self.uncover_column(smallest_id);
+
+        return count;
+    }
+
+    /// Lazily enumerates every exact cover of the network. Each call to `next` resumes the search
+    /// where the previous solution was found, and the network is restored once the iterator is
+    /// dropped.
+    pub fn solutions(&self, column_header_root_id: usize) -> SolutionIterator<'_, R, C> {
+        SolutionIterator {
+            network: self,
+            search: Search::new(column_header_root_id),
+        }
+    }
+}
+
+impl SearchStats {
+    pub fn new() -> SearchStats {
+        SearchStats::default()
+    }
+
+    fn visit(&mut self, depth: usize) {
+        self.nodes += 1;
+        self.max_depth = self.max_depth.max(depth);
+        if self.levels.len() <= depth {
+            self.levels.resize(depth + 1, LevelStats::default());
+        }
+        self.levels[depth].nodes += 1;
+    }
+
+    fn branch(&mut self, depth: usize, size: usize) {
+        self.levels[depth].branches += size;
+        if size > 1 {
+            self.guesses += 1;
+        }
+    }
+
+    /// The average number of rows the nodes at `depth` could choose from.
+    pub fn branching_factor(&self, depth: usize) -> Option<f64> {
+        let level = self.levels.get(depth)?;
+        if level.nodes == 0 {
+            return None;
+        }
+        Some(level.branches as f64 / level.nodes as f64)
+    }
+}
+
+//State of an iterative Algorithm X search that can be paused after every solution
+pub struct Search {
+    column_header_root_id: usize,
+    //The column covered at each depth and the field whose row is currently part of the solution
+    stack: Vec<(usize, usize)>,
+    //With shuffled rows, the fields of each covered column that are still left to try
+    untried: Vec<Vec<usize>>,
+    random: Option<Random>,
+    has_started: bool,
+    is_finished: bool,
+}
+
+impl Search {
+    pub fn new(column_header_root_id: usize) -> Search {
+        Search {
+            column_header_root_id,
+            stack: Vec::new(),
+            untried: Vec::new(),
+            random: None,
+            has_started: false,
+            is_finished: false,
+        }
+    }
+
+    /// Tries the rows of every covered column in a random order instead of from the top down, so
+    /// that each seed leads to a different first solution.
+    pub fn shuffle_rows(mut self, random: Random) -> Search {
+        self.random = Some(random);
+        self
+    }
+
+    pub fn next_solution<R: Clone, C: Clone>(&mut self, network: &Network<R, C>) -> Option<Vec<R>> {
+        if self.is_finished {
+            return None;
+        }
+        //Resume by trying the next alternative to the previously returned solution
+        if self.has_started && !self.advance(network) {
+            self.is_finished = true;
+            return None;
+        }
+        self.has_started = true;
+
+        loop {
+            let smallest_id = network.smallest_column(self.column_header_root_id);
+
+            //Solution has been found! ... Empty matrix
+            if smallest_id == self.column_header_root_id {
+                return Some(self.stack.iter().map(|&(_, field_id)| network.get_row(field_id)).collect());
+            }
+
+            //Impossible configuration... Backtrack
+            if network.get_column_header_size(smallest_id) == 0 {
+                if !self.advance(network) {
+                    self.is_finished = true;
+                    return None;
+                }
+                continue;
+            }
+
+            //Cover the smallest header and pick its first row
+            network.cover_column(smallest_id);
+            let field_id = match self.random {
+                Some(ref mut random) => {
+                    let mut field_ids: Vec<usize> = network.vertical_iter(smallest_id).skip(1).collect();
+                    random.shuffle(&mut field_ids);
+                    let field_id = field_ids.pop().unwrap();
+                    self.untried.push(field_ids);
+                    field_id
+                },
+                None => network.get_node(smallest_id).borrow().down_id,
+            };
+            network.cover_row(field_id);
+            self.stack.push((smallest_id, field_id));
+        }
+    }
+
+    //Replaces the deepest row of the partial solution with the next row of its column, backtracking
+    //through exhausted columns. Returns false once every alternative has been tried.
+    fn advance<R: Clone, C: Clone>(&mut self, network: &Network<R, C>) -> bool {
+        while let Some((column_id, field_id)) = self.stack.pop() {
+            network.uncover_row(field_id);
+
+            let next_field_id = match self.random {
+                Some(_) => self.untried.last_mut().and_then(|field_ids| field_ids.pop()),
+                None => Some(network.get_node(field_id).borrow().down_id).filter(|next_id| *next_id != column_id),
+            };
+            if let Some(next_field_id) = next_field_id {
+                network.cover_row(next_field_id);
+                self.stack.push((column_id, next_field_id));
+                return true;
+            }
+
+            network.uncover_column(column_id);
+            self.untried.pop();
+        }
+
+        return false;
+    }
+
+    //Uncovers everything the search has covered so far
+    pub fn reset<R: Clone, C: Clone>(&mut self, network: &Network<R, C>) {
+        while let Some((column_id, field_id)) = self.stack.pop() {
+            network.uncover_row(field_id);
+            network.uncover_column(column_id);
+        }
+        self.untried.clear();
+        self.is_finished = true;
+    }
+}
+
+pub struct SolutionIterator<'a, R: 'a + Clone, C: 'a + Clone> {
+    network: &'a Network<R, C>,
+    search: Search,
+}
+
+impl<'a, R: Clone, C: Clone> Iterator for SolutionIterator<'a, R, C> {
+    type Item = Vec<R>;
+
+    fn next(&mut self) -> Option<Vec<R>> {
+        self.search.next_solution(self.network)
+    }
+}
+
+impl<'a, R: Clone, C: Clone> Drop for SolutionIterator<'a, R, C> {
+    fn drop(&mut self) {
+        self.search.reset(self.network);
+    }
+}
+
+impl<'a, R: Clone, C: Clone> Iterator for HorizontalNodeIterator<'a, R, C> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let current_node_id = self.current_node;
+        let current_node = self.network.get_node(current_node_id);
+        let next_node_id = current_node.borrow().right_id;
+
+        if !self.has_started {
+            self.has_started = true;
+            return Some(current_node_id);
+        }
+
+        self.current_node = next_node_id;
+        if next_node_id == self.start_node {
+            return None;
+        }
+
+        return Some(next_node_id);
+    }
+}
+
+impl<'a, R: Clone, C: Clone> Iterator for VerticalNodeIterator<'a, R, C> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let current_node_id = self.current_node;
+        let current_node = self.network.get_node(current_node_id);
+        let next_node_id = current_node.borrow().down_id;
+
+        if !self.has_started {
+            self.has_started = true;
+            return Some(current_node_id);
+        }
+
+        self.current_node = next_node_id;
+        if next_node_id == self.start_node {
+            return None;
+        }
+
+        return Some(next_node_id);
+    }
+}
+
+
+impl<R: Clone, C: Clone + Eq + Hash> Default for ExactCover<R, C> {
+    fn default() -> ExactCover<R, C> {
+        ExactCover::new()
+    }
+}
+
+impl<R: Clone, C: Clone + Eq + Hash> ExactCover<R, C> {
+    pub fn new() -> ExactCover<R, C> {
+        let mut network = Network::new();
+        let column_header_root_id = network.create_node(NodeType::Root);
+
+        ExactCover {
+            network,
+            column_header_root_id,
+            columns: HashMap::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Declares a column that every solution has to cover exactly once. Declaring the same column
+    /// again has no effect.
+    pub fn add_column(&mut self, column: C) {
+        if self.columns.contains_key(&column) {
+            return;
+        }
+        let new_header_id = self.network.create_node(NodeType::ColumnHeader(column.clone(), 0));
+        //Keep the columns in the order they were declared
+        let last_header_id = self.network.get_node(self.column_header_root_id).borrow().left_id;
+        self.network.append_node_horizontally(last_header_id, new_header_id);
+        self.columns.insert(column, new_header_id);
+    }
+
+    /// Adds a row covering the given columns, which must all have been declared with `add_column`.
+    pub fn add_row(&mut self, row: R, columns: &[C]) {
+        let mut previous_field_id = None;
+        for column in columns.iter() {
+            let column_header_id = match self.columns.get(column) {
+                Some(column_header_id) => *column_header_id,
+                None => panic!("Row covers a column that has not been declared!"),
+            };
+            let field_id = self.network.create_node(NodeType::Field(row.clone(), column_header_id));
+
+            match previous_field_id {
+                Some(previous_field_id) => self.network.append_node_horizontally(previous_field_id, field_id),
+                None => self.rows.push(field_id),
+            }
+            previous_field_id = Some(field_id);
+        }
+    }
+
+    //The number of rows covering at least one column
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The `index`th row covering at least one column, counting in the order they were added.
+    pub fn row(&self, index: usize) -> R {
+        self.network.get_row(self.rows[index])
+    }
+
+    pub fn has_column(&self, column: &C) -> bool {
+        self.columns.contains_key(column)
+    }
+
+    pub fn network(&self) -> &Network<R, C> {
+        &self.network
+    }
+
+    pub fn column_header_root_id(&self) -> usize {
+        self.column_header_root_id
+    }
+
+    /// Finds the first solution, or `None` when the columns cannot be covered exactly.
+    pub fn solve(&self) -> Option<Vec<R>> {
+        self.solutions().next()
+    }
+
+    /// Finds the first solution like `solve`, along with the statistics of the search.
+    pub fn solve_with_stats(&self) -> (Option<Vec<R>>, SearchStats) {
+        let mut solution = Vec::new();
+        let mut stats = SearchStats::new();
+        if self.network.solve_exact_cover(self.column_header_root_id, &mut solution, &mut stats) {
+            return (Some(solution), stats);
+        }
+        (None, stats)
+    }
+
+    /// Finds a random solution, the same one for the same state of `random`.
+    pub fn solve_randomized(&self, random: &mut Random) -> Option<Vec<R>> {
+        let search = Search::new(self.column_header_root_id).shuffle_rows(Random::new(random.next_u64()));
+        let mut solutions = SolutionIterator {
+            network: &self.network,
+            search,
+        };
+        solutions.next()
+    }
+
+    /// Finds a solution that contains the `index`th row (see `row`), or `None` if no solution does.
+    pub fn solve_containing(&self, index: usize) -> Option<Vec<R>> {
+        let mut solution = Vec::new();
+        let mut stats = SearchStats::new();
+        if self.network.solve_exact_cover_containing(self.column_header_root_id, self.rows[index], &mut solution, &mut stats) {
+            return Some(solution);
+        }
+        None
+    }
+
+    /// Finds up to `limit` solutions in the order the search comes across them.
+    pub fn solve_up_to(&self, limit: usize) -> Vec<Vec<R>> {
+        self.solutions().take(limit).collect()
+    }
+
+    /// Lazily enumerates every solution.
+    pub fn solutions(&self) -> SolutionIterator<'_, R, C> {
+        self.network.solutions(self.column_header_root_id)
+    }
+
+    /// Counts the solutions, stopping the search as soon as `limit` have been found.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        self.network.count_exact_covers(self.column_header_root_id, limit)
+    }
+}
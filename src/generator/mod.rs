@@ -0,0 +1,262 @@
+//! Generates puzzles with a unique solution.
+//!
+//! ```
+//! use sudoku_solver::generator::Generator;
+//! use sudoku_solver::rating::Tier;
+//! use sudoku_solver::sudoku_solver_dancinglinks::has_unique_solution;
+//!
+//! let puzzle = Generator::new(7).target_clues(30).generate();
+//! assert!(puzzle.board.clue_count() >= 30);
+//! assert!(has_unique_solution(&puzzle.board));
+//! assert_eq!(puzzle, Generator::new(7).target_clues(30).generate());
+//!
+//! //Keep generating until a puzzle lands in the requested tiers
+//! let rated = Generator::new(7).difficulty(Tier::Easy, Tier::Medium).generate_rated().unwrap();
+//! assert!(rated.rating.tier <= Tier::Medium);
+//! ```
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use board::{Board, Geometry};
+use random::Random;
+use rating::{rate_up_to, Rating, Tier};
+use sudoku_solver_dancinglinks::{has_unique_solution, random_solution};
+
+//Which cells have to be emptied together so that the givens keep their shape
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub enum Symmetry {
+    None,
+    //Turning the board by 180 or 90 degrees
+    Rotational180,
+    Rotational90,
+    //Flipping the board upside down or left to right
+    Horizontal,
+    Vertical,
+    //Mirroring on the diagonal from the top left or the top right corner
+    Diagonal,
+    AntiDiagonal,
+}
+
+/// Builds puzzles by filling an empty grid at random and then removing clues in a random order
+/// for as long as the solution stays unique. The same seed always yields the same puzzle.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Generator {
+    seed: u64,
+    geometry: Geometry,
+    target_clues: usize,
+    symmetry: Symmetry,
+    //The easiest and hardest tier to accept
+    difficulty: (Tier, Tier),
+    max_attempts: usize,
+    time_limit: Option<Duration>,
+}
+
+//A generated puzzle along with its solution and the seed it was made from
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Puzzle {
+    pub board: Board,
+    pub solution: Board,
+    pub seed: u64,
+}
+
+//A puzzle within the requested difficulty and how many puzzles were thrown away before it
+#[derive(PartialEq, Debug, Clone)]
+pub struct RatedPuzzle {
+    pub puzzle: Puzzle,
+    pub rating: Rating,
+    pub discarded: usize,
+}
+
+//Returned when no puzzle within the requested difficulty turned up before the budget ran out
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct BudgetExhausted {
+    pub discarded: usize,
+}
+
+impl Generator {
+    /// A generator for 9x9 boards that removes as many clues as it can.
+    pub fn new(seed: u64) -> Generator {
+        Generator {
+            seed,
+            geometry: Geometry::new(3, 3),
+            target_clues: 0,
+            symmetry: Symmetry::None,
+            difficulty: (Tier::Easy, Tier::Diabolical),
+            max_attempts: 100,
+            time_limit: None,
+        }
+    }
+
+    pub fn geometry(mut self, geometry: Geometry) -> Generator {
+        self.geometry = geometry;
+        self
+    }
+
+    /// Stops removing clues once the puzzle is down to `target_clues`. The target is not always
+    /// reachable, in that case the puzzle keeps every clue whose removal would break uniqueness.
+    pub fn target_clues(mut self, target_clues: usize) -> Generator {
+        self.target_clues = target_clues;
+        self
+    }
+
+    /// Removes clues in groups that map onto each other under `symmetry`, so the givens of the
+    /// puzzle look the same after turning or mirroring the board.
+    pub fn symmetry(mut self, symmetry: Symmetry) -> Generator {
+        self.symmetry = symmetry;
+        self
+    }
+
+    /// Only accepts puzzles rated from `easiest` up to `hardest`. Clues whose removal would make
+    /// the puzzle harder than `hardest` are kept, puzzles easier than `easiest` are discarded by
+    /// `generate_rated`.
+    pub fn difficulty(mut self, easiest: Tier, hardest: Tier) -> Generator {
+        self.difficulty = (easiest, hardest);
+        self
+    }
+
+    /// How many puzzles `generate_rated` may try, 100 by default.
+    pub fn max_attempts(mut self, max_attempts: usize) -> Generator {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// How long `generate_rated` may keep trying. A puzzle is always finished once it is started,
+    /// so the limit can be overrun by the time one attempt takes.
+    pub fn time_limit(mut self, time_limit: Duration) -> Generator {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Generates a single puzzle from the seed, no harder than the difficulty allows.
+    pub fn generate(&self) -> Puzzle {
+        let mut random = Random::new(self.seed);
+        let solution = random_solution(&Board::empty(self.geometry), &mut random)
+            .expect("An empty board always has a solution!");
+        let hardest = match self.difficulty.1 {
+            Tier::Diabolical => None,
+            hardest => Some(hardest),
+        };
+        let board = remove_clues(&solution, self.target_clues, self.symmetry, hardest, &mut random);
+
+        Puzzle {
+            board,
+            solution,
+            seed: self.seed,
+        }
+    }
+
+    /// Generates puzzles until one is rated within the difficulty range or the attempts or time
+    /// run out. Every attempt after the first draws a new seed from the previous one, the seed of
+    /// the returned puzzle reproduces it with `generate`.
+    pub fn generate_rated(&self) -> Result<RatedPuzzle, BudgetExhausted> {
+        let start = Instant::now();
+        let mut seeds = Random::new(self.seed);
+        let mut generator = *self;
+        let mut discarded = 0;
+
+        for _ in 0..self.max_attempts {
+            if self.time_limit.is_some_and(|time_limit| start.elapsed() >= time_limit) {
+                break;
+            }
+
+            let puzzle = generator.generate();
+            match rate_up_to(&puzzle.board, self.difficulty.1) {
+                Some(rating) if rating.tier >= self.difficulty.0 => {
+                    return Ok(RatedPuzzle {
+                        puzzle,
+                        rating,
+                        discarded,
+                    });
+                },
+                _ => discarded += 1,
+            }
+            generator.seed = seeds.next_u64();
+        }
+
+        Err(BudgetExhausted {
+            discarded,
+        })
+    }
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 7] = [Symmetry::None, Symmetry::Rotational180, Symmetry::Rotational90,
+                                    Symmetry::Horizontal, Symmetry::Vertical, Symmetry::Diagonal,
+                                    Symmetry::AntiDiagonal];
+
+    /// The cells that `idx` is mapped onto on a board with `side` rows, starting with `idx` itself.
+    pub fn orbit(self, side: usize, idx: usize) -> Vec<usize> {
+        let (row, col) = (idx / side, idx % side);
+        let last = side - 1;
+        let images = match self {
+            Symmetry::None => vec![],
+            Symmetry::Rotational180 => vec![(last - row, last - col)],
+            Symmetry::Rotational90 => vec![(col, last - row), (last - row, last - col), (last - col, row)],
+            Symmetry::Horizontal => vec![(last - row, col)],
+            Symmetry::Vertical => vec![(row, last - col)],
+            Symmetry::Diagonal => vec![(col, row)],
+            Symmetry::AntiDiagonal => vec![(last - col, last - row)],
+        };
+
+        let mut orbit = vec![idx];
+        for (row, col) in images {
+            let image = row * side + col;
+            if !orbit.contains(&image) {
+                orbit.push(image);
+            }
+        }
+        orbit
+    }
+}
+
+//Empties the cells of a solved board in a random order, a whole symmetry orbit at a time, putting
+//back every orbit the solution cannot do without or that would make the puzzle harder than
+//hardest, until target_clues are left or no orbit can go
+fn remove_clues(solution: &Board, target_clues: usize, symmetry: Symmetry, hardest: Option<Tier>,
+                random: &mut Random) -> Board {
+    let side = solution.side();
+    let mut cells = solution.cells().to_vec();
+    let mut order: Vec<usize> = (0..cells.len()).collect();
+    random.shuffle(&mut order);
+
+    let mut clue_count = cells.len();
+    for idx in order {
+        if clue_count <= target_clues {
+            break;
+        }
+
+        //Orbits that were already emptied, or would take the puzzle below the target, are skipped
+        let orbit: Vec<usize> = symmetry.orbit(side, idx).into_iter().filter(|cell| cells[*cell] != 0).collect();
+        if orbit.is_empty() || clue_count - orbit.len() < target_clues {
+            continue;
+        }
+
+        for cell in orbit.iter() {
+            cells[*cell] = 0;
+        }
+        let board = Board::from_cells_with_geometry(cells.clone(), solution.geometry());
+        //Rating is only worth it once the solution is known to stay unique
+        let can_go = has_unique_solution(&board) && match hardest {
+            Some(hardest) => rate_up_to(&board, hardest).is_some(),
+            None => true,
+        };
+        if can_go {
+            clue_count -= orbit.len();
+        } else {
+            for cell in orbit.iter() {
+                cells[*cell] = solution.cells()[*cell];
+            }
+        }
+    }
+
+    return Board::from_cells_with_geometry(cells, solution.geometry());
+}
+
+impl fmt::Display for BudgetExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no puzzle within the difficulty range after discarding {}", self.discarded)
+    }
+}
+
+impl Error for BudgetExhausted {}
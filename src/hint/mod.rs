@@ -0,0 +1,257 @@
+//! Hints: the next deduction for a board a player is working on.
+//!
+//! ```
+//! use sudoku_solver::Board;
+//! use sudoku_solver::candidates::Candidates;
+//! use sudoku_solver::hint::{next_hint, HintResult};
+//!
+//! let board = Board::new("200005709900070000070001050008200000067000520000008100040300080000060002609800003").unwrap();
+//! match next_hint(&board, &Candidates::from_board(&board)) {
+//!     HintResult::Hint(hint) => println!("{}", hint.explanation),
+//!     _ => unreachable!(),
+//! }
+//! ```
+use std::fmt::Display;
+
+use board::{Board, Cell};
+use candidates::{Candidate, Candidates};
+use logical_solver::{Detail, LogicalSolver, Step, Technique};
+use rating::techniques_by_rating;
+use sudoku_solver_dancinglinks::solutions;
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum HintResult {
+    Hint(Box<Hint>),
+    //Every cell is filled in
+    Solved,
+    //The entries or pencil marks already rule out the solution
+    Contradiction(Contradiction),
+    //None of the techniques apply to the pencil marks
+    NoDeduction,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Hint {
+    pub technique: Technique,
+    //The cells the hint places a value in or removes candidates from
+    pub targets: Vec<Cell>,
+    pub placements: Vec<Candidate>,
+    pub eliminations: Vec<Candidate>,
+    pub explanation: String,
+    //The whole step, e.g. to highlight the cells of its pattern
+    pub step: Step,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Contradiction {
+    //The cells at fault, when they can be singled out
+    pub cells: Vec<Cell>,
+    pub explanation: String,
+}
+
+/// Finds the simplest deduction for `board`, the givens and the entries of the player, starting
+/// from the pencil marks in `candidates`. Entries of the board missing from the pencil marks are
+/// placed first, entries the pencil marks place a different value in are a contradiction.
+/// Uniqueness techniques are used when the board has a unique solution.
+pub fn next_hint(board: &Board, candidates: &Candidates) -> HintResult {
+    let geometry = board.geometry();
+    if candidates.geometry() != geometry {
+        return HintResult::Contradiction(Contradiction {
+            cells: Vec::new(),
+            explanation: format!("The board has {} blocks but the pencil marks have {} blocks.", geometry,
+                                 candidates.geometry()),
+        });
+    }
+
+    let disagreeing: Vec<Cell> = board.cells().iter().enumerate()
+        .filter(|&(idx, value)| *value != 0 && candidates.value(idx) != 0 && candidates.value(idx) != *value)
+        .map(|(idx, _)| geometry.cell_at(idx))
+        .collect();
+    if !disagreeing.is_empty() {
+        let explanation = format!("The entries of {} differ from the values placed in the pencil marks.", join(&disagreeing));
+        return HintResult::Contradiction(Contradiction { cells: disagreeing, explanation });
+    }
+
+    let mut candidates = candidates.clone();
+    for (idx, value) in board.cells().iter().enumerate() {
+        if *value != 0 && candidates.value(idx) == 0 {
+            candidates.place(idx, *value);
+        }
+    }
+    let board = candidates.to_board();
+
+    let duplicates = duplicate_entries(&board);
+    if !duplicates.is_empty() {
+        let explanation = format!("{} hold the same value in one unit.", join(&duplicates));
+        return HintResult::Contradiction(Contradiction { cells: duplicates, explanation });
+    }
+
+    let found: Vec<Board> = solutions(&board).take(2).collect();
+    if found.is_empty() {
+        return HintResult::Contradiction(Contradiction {
+            cells: Vec::new(),
+            explanation: "The entries leave no way to complete the board.".to_string(),
+        });
+    }
+    //With a single solution, pencil marks missing the value of their cell are wrong
+    if found.len() == 1 {
+        let missing: Vec<Cell> = found[0].cells().iter().enumerate()
+            .filter(|&(idx, value)| candidates.value(idx) == 0 && !candidates.has(idx, *value))
+            .map(|(idx, _)| geometry.cell_at(idx))
+            .collect();
+        if !missing.is_empty() {
+            let explanation = format!("The pencil marks of {} rule out the value that belongs there.", join(&missing));
+            return HintResult::Contradiction(Contradiction { cells: missing, explanation });
+        }
+    }
+
+    if candidates.is_solved() {
+        return HintResult::Solved;
+    }
+
+    let solver = LogicalSolver::with_techniques(&techniques_by_rating()).assume_uniqueness(true);
+    match solver.next_step(&candidates) {
+        Some(step) => HintResult::Hint(Box::new(hint(step))),
+        None => HintResult::NoDeduction,
+    }
+}
+
+/// A short English explanation of a step: the pattern it relies on followed by what it changes.
+pub fn explain(step: &Step) -> String {
+    format!("{} {}", explain_pattern(step), explain_result(step))
+}
+
+fn hint(step: Step) -> Hint {
+    let mut targets: Vec<Cell> = Vec::new();
+    for candidate in step.placements.iter().chain(step.eliminations.iter()) {
+        if !targets.contains(&candidate.cell) {
+            targets.push(candidate.cell);
+        }
+    }
+
+    Hint {
+        technique: step.technique,
+        targets,
+        placements: step.placements.clone(),
+        eliminations: step.eliminations.clone(),
+        explanation: explain(&step),
+        step,
+    }
+}
+
+fn explain_pattern(step: &Step) -> String {
+    let cells = join(&step.cells);
+    let values = join(&step.values);
+    let first_value = step.values.first().cloned().unwrap_or(0);
+
+    match step.detail {
+        Detail::Fish { ref base_units, ref cover_units, ref fins } => {
+            let mut pattern = format!("Within {}, {} only fits in {}", join(base_units), first_value, join(cover_units));
+            if !fins.is_empty() {
+                pattern += &format!(" apart from the fins at {}", join(fins));
+            }
+            return format!("{}, so {} get their {} from {}.", pattern, join(cover_units), first_value, join(base_units));
+        },
+        Detail::Chain(ref chain) => {
+            return format!("In the chain {}, = means at least one of two candidates is true and - at most one, so one of its ends is true.", chain);
+        },
+        Detail::Als { ref sets, ref restricted_commons, ref stem } => {
+            let sets: Vec<String> = sets.iter().map(|set| format!("{{{}}}", set)).collect();
+            return match *stem {
+                Some(stem) => format!("Whichever of {} goes in {}, one of the sets {} loses it and has to hold {}.",
+                                      join(restricted_commons), stem, sets.join(", "), first_value),
+                None => format!("The almost locked sets {} are linked by {}, so one of them has to hold {}.",
+                                sets.join(", "), join(restricted_commons), first_value),
+            };
+        },
+        Detail::Forcing(ref branches) => {
+            let branches: Vec<String> = branches.iter().map(|branch| branch.to_string()).collect();
+            return if branches.len() == 1 {
+                format!("Assuming otherwise leads to a contradiction: {}.", branches[0])
+            } else {
+                format!("Every possibility leads to the same result: {}.", branches.join(", "))
+            };
+        },
+        Detail::None => {},
+    }
+
+    let unit = step.units.first().map(|unit| unit.to_string()).unwrap_or_default();
+    match step.technique {
+        Technique::HiddenSingle => format!("{} can only go in {} within {}.", values, cells, unit),
+        Technique::NakedSingle => format!("{} has no candidate left other than {}.", cells, values),
+        Technique::PointingCandidates | Technique::ClaimingCandidates => {
+            let line = step.units.get(1).map(|unit| unit.to_string()).unwrap_or_default();
+            format!("Within {}, {} can only go in {}, which all lie in {}.", unit, values, cells, line)
+        },
+        Technique::NakedPair | Technique::NakedTriple | Technique::NakedQuad => {
+            format!("{} can only hold {} between them, so no other cell of {} can.", cells, values, unit)
+        },
+        Technique::HiddenPair | Technique::HiddenTriple | Technique::HiddenQuad => {
+            format!("{} can only go in {} within {}, so those cells hold nothing else.", values, cells, unit)
+        },
+        Technique::XYZWing => {
+            let value = step.eliminations.first().map_or(0, |elimination| elimination.value);
+            format!("{} holds {} and sees {}, which each hold {} and one of its other values, so one of the three is {}.",
+                    step.cells[0], values, join(&step.cells[1..]), value, value)
+        },
+        Technique::BugPlusOne => {
+            format!("Every other open cell has two candidates, so {} must take {} to keep the solution unique.", cells, values)
+        },
+        technique if technique.requires_uniqueness() => {
+            format!("{} could form a deadly pattern on {}, which would allow a second solution.", cells, values)
+        },
+        _ => format!("{} on {} in {}.", step.technique, values, cells),
+    }
+}
+
+fn explain_result(step: &Step) -> String {
+    let mut sentences = Vec::new();
+    for placement in step.placements.iter() {
+        sentences.push(format!("Place {} in {}.", placement.value, placement.cell));
+    }
+
+    //Group the eliminations by value
+    let mut values: Vec<u8> = step.eliminations.iter().map(|elimination| elimination.value).collect();
+    values.sort();
+    values.dedup();
+    for value in values {
+        let cells: Vec<Cell> = step.eliminations.iter()
+            .filter(|elimination| elimination.value == value)
+            .map(|elimination| elimination.cell)
+            .collect();
+        sentences.push(format!("Remove {} from {}.", value, join(&cells)));
+    }
+
+    sentences.join(" ")
+}
+
+//The cells of entries repeating a value within a row, column or block
+fn duplicate_entries(board: &Board) -> Vec<Cell> {
+    let geometry = board.geometry();
+    let mut cells = Vec::new();
+    for unit in geometry.units() {
+        let unit_cells = geometry.unit_cells(unit);
+        for idx1 in unit_cells.iter() {
+            let value = board.cells()[*idx1];
+            if value == 0 {
+                continue;
+            }
+            let cell = geometry.cell_at(*idx1);
+            if unit_cells.iter().any(|idx2| idx2 != idx1 && board.cells()[*idx2] == value) && !cells.contains(&cell) {
+                cells.push(cell);
+            }
+        }
+    }
+    cells.sort();
+    cells
+}
+
+//Lists items in English: "a", "a and b", "a, b and c"
+fn join<T: Display>(items: &[T]) -> String {
+    let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+    match items.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+    }
+}
@@ -0,0 +1,63 @@
+//! Backtracking and Dancing Links sudoku solvers.
+//!
+//! ```
+//! let solution = sudoku_solver::solve("200005709900070000070001050008200000067000520000008100040300080000060002609800003").unwrap();
+//! assert!(solution.is_complete());
+//!
+//! assert!(sudoku_solver::solve("123").is_err());
+//! assert!(sudoku_solver::solve("12345678.........9...............................................................").is_err());
+//! ```
+#![allow(clippy::needless_return)]
+
+pub mod board;
+pub mod candidates;
+pub mod exact_cover;
+pub mod generator;
+pub mod hint;
+pub mod logical_solver;
+pub mod random;
+pub mod rating;
+pub mod setter;
+pub mod sudoku_solver_dancinglinks;
+pub mod sudoku_solver_backtrack;
+
+use std::error;
+use std::fmt;
+
+pub use board::{Board, ParseError, Unsolvable};
+
+//Everything that can go wrong when solving a board string
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Error {
+    Parse(ParseError),
+    Unsolvable(Unsolvable),
+}
+
+/// Parses `board_string` (see `Board::new`) and solves it with the dancing links solver.
+pub fn solve(board_string: &str) -> Result<Board, Error> {
+    let board = Board::new(board_string)?;
+    Ok(sudoku_solver_dancinglinks::solve_sudoku(&board)?)
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Error {
+        Error::Parse(error)
+    }
+}
+
+impl From<Unsolvable> for Error {
+    fn from(error: Unsolvable) -> Error {
+        Error::Unsolvable(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Parse(ref error) => write!(f, "{}", error),
+            Error::Unsolvable(ref error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl error::Error for Error {}
@@ -0,0 +1,352 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use board::{Cell, Unit};
+use candidates::{mask_values, Candidate, Candidates};
+use logical_solver::{Detail, Layout, Step, Technique};
+
+//Larger sets are rarely needed. The search for sets abandons a subset as soon as its cells hold
+//more values than a set of this size could, which keeps it manageable on large boards.
+const MAX_ALS_CELLS: usize = 6;
+
+/// N cells of a unit with N + 1 candidates between them: removing any one of the candidates
+/// leaves a locked set.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct AlmostLockedSet {
+    pub cells: Vec<Cell>,
+    pub values: Vec<u8>,
+    pub unit: Unit,
+}
+
+//A set of cell indices as a bitset
+#[derive(Eq, PartialEq, Debug, Clone)]
+struct CellSet {
+    words: Vec<u64>,
+}
+
+impl CellSet {
+    fn new(cell_count: usize) -> CellSet {
+        CellSet {
+            words: vec![0; cell_count / 64 + 1],
+        }
+    }
+
+    fn insert(&mut self, idx: usize) {
+        self.words[idx / 64] |= 1 << (idx % 64);
+    }
+
+    fn intersection(&self, other: &CellSet) -> CellSet {
+        CellSet {
+            words: self.words.iter().zip(other.words.iter()).map(|(word1, word2)| word1 & word2).collect(),
+        }
+    }
+
+    fn is_disjoint(&self, other: &CellSet) -> bool {
+        self.words.iter().zip(other.words.iter()).all(|(word1, word2)| word1 & word2 == 0)
+    }
+
+    fn is_subset(&self, other: &CellSet) -> bool {
+        self.words.iter().zip(other.words.iter()).all(|(word1, word2)| word1 & !word2 == 0)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    fn indices(&self) -> Vec<usize> {
+        let mut indices = Vec::new();
+        for (position, word) in self.words.iter().enumerate() {
+            let mut word = *word;
+            while word != 0 {
+                indices.push(position * 64 + word.trailing_zeros() as usize);
+                word &= word - 1;
+            }
+        }
+        indices
+    }
+}
+
+struct Als {
+    unit_idx: usize,
+    cells: Vec<usize>,
+    cell_set: CellSet,
+    mask: u32,
+    //Indexed by value: the cells of the set holding the value, and the cells seeing all of those
+    value_cells: Vec<CellSet>,
+    seen_by: Vec<CellSet>,
+}
+
+//Everything the rules need about the grid, computed once per search
+struct Grid {
+    sets: Vec<Als>,
+    peers: Vec<CellSet>,
+    //Indexed by value: the open cells still holding it
+    value_cells: Vec<CellSet>,
+}
+
+//Extends subset with the cells of open, in order, collecting every almost locked set along the way.
+//Adding cells never removes values, so a subset holding more values than the largest set allows
+//cannot be extended into one.
+fn collect_sets(candidates: &Candidates, open: &[usize], subset: &mut Vec<usize>, mask: u32,
+                found: &mut Vec<(Vec<usize>, u32)>) {
+    for (position, idx) in open.iter().enumerate() {
+        let extended = mask | candidates.mask(*idx);
+        if extended.count_ones() as usize > MAX_ALS_CELLS + 1 {
+            continue;
+        }
+
+        subset.push(*idx);
+        if extended.count_ones() as usize == subset.len() + 1 {
+            found.push((subset.clone(), extended));
+        }
+        if subset.len() < MAX_ALS_CELLS {
+            collect_sets(candidates, &open[position + 1..], subset, extended, found);
+        }
+        subset.pop();
+    }
+}
+
+impl Grid {
+    fn new(candidates: &Candidates, layout: &Layout) -> Grid {
+        let cell_count = layout.geometry.cell_count();
+        let side = layout.geometry.side();
+
+        let mut peers = vec![CellSet::new(cell_count); cell_count];
+        for (idx1, set) in peers.iter_mut().enumerate() {
+            for idx2 in 0..cell_count {
+                if layout.sees(idx1, idx2) {
+                    set.insert(idx2);
+                }
+            }
+        }
+
+        let mut value_cells = vec![CellSet::new(cell_count); side + 1];
+        for idx in 0..cell_count {
+            for value in mask_values(candidates.mask(idx)) {
+                if candidates.value(idx) == 0 {
+                    value_cells[value as usize].insert(idx);
+                }
+            }
+        }
+
+        let mut sets = Vec::new();
+        let mut seen = HashSet::new();
+        for (unit_idx, cells) in layout.unit_cells.iter().enumerate() {
+            //Cells with too many candidates can never be part of a set
+            let open: Vec<usize> = cells.iter().cloned()
+                .filter(|idx| candidates.value(*idx) == 0 && candidates.count(*idx) as usize <= MAX_ALS_CELLS + 1)
+                .collect();
+            let mut found = Vec::new();
+            collect_sets(candidates, &open, &mut Vec::new(), 0, &mut found);
+            for (subset, mask) in found {
+                //A row and a block can share the same set
+                if seen.insert(subset.clone()) {
+                    sets.push(Als::new(unit_idx, subset, mask, candidates, &peers, side));
+                }
+            }
+        }
+        //Smaller sets first so that the simplest patterns are found first
+        sets.sort_by_key(|als| als.cells.len());
+
+        Grid {
+            sets,
+            peers,
+            value_cells,
+        }
+    }
+
+    //The candidates removed by a value that has to appear in the cells of one of the sets
+    fn eliminations(&self, sets: &[&Als], value: u8) -> CellSet {
+        sets.iter().fold(self.value_cells[value as usize].clone(), |targets, als| {
+            targets.intersection(&als.seen_by[value as usize])
+        })
+    }
+}
+
+impl Als {
+    fn new(unit_idx: usize, cells: Vec<usize>, mask: u32, candidates: &Candidates, peers: &[CellSet], side: usize) -> Als {
+        let cell_count = peers.len();
+        let mut cell_set = CellSet::new(cell_count);
+        let mut value_cells = vec![CellSet::new(cell_count); side + 1];
+        let mut seen_by = vec![CellSet::new(cell_count); side + 1];
+
+        for value in mask_values(mask) {
+            let holders: Vec<usize> = cells.iter().cloned().filter(|idx| candidates.has(*idx, value)).collect();
+            let mut seeing = peers[holders[0]].clone();
+            for idx in holders.iter() {
+                value_cells[value as usize].insert(*idx);
+                seeing = seeing.intersection(&peers[*idx]);
+            }
+            seen_by[value as usize] = seeing;
+        }
+        for idx in cells.iter() {
+            cell_set.insert(*idx);
+        }
+
+        Als {
+            unit_idx,
+            cells,
+            cell_set,
+            mask,
+            value_cells,
+            seen_by,
+        }
+    }
+
+    //Values that can be true in at most one of two disjoint sets, as every cell holding it in
+    //one of them sees every cell holding it in the other
+    fn restricted_commons(&self, other: &Als) -> Vec<u8> {
+        if !self.cell_set.is_disjoint(&other.cell_set) {
+            return Vec::new();
+        }
+        mask_values(self.mask & other.mask).into_iter()
+            .filter(|value| other.value_cells[*value as usize].is_subset(&self.seen_by[*value as usize]))
+            .collect()
+    }
+
+    fn describe(&self, layout: &Layout) -> AlmostLockedSet {
+        AlmostLockedSet {
+            cells: self.cells.iter().map(|idx| layout.cell(*idx)).collect(),
+            values: mask_values(self.mask),
+            unit: layout.units[self.unit_idx],
+        }
+    }
+}
+
+//Two sets linked by a restricted common X: one of them is a locked set without X, so a value Z
+//they share has to be in one of them and cells seeing all of their Z cells lose it
+pub fn als_xz(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let grid = Grid::new(candidates, layout);
+
+    for (position, als1) in grid.sets.iter().enumerate() {
+        for als2 in grid.sets[position + 1..].iter() {
+            for x in als1.restricted_commons(als2) {
+                for z in mask_values(als1.mask & als2.mask & !(1 << x)) {
+                    let targets = grid.eliminations(&[als1, als2], z);
+                    if !targets.is_empty() {
+                        return Some(als_step(Technique::AlsXz, layout, &[als1, als2], &[x], None, z, &targets));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+//A pivot set linked to two sets by the restricted commons X and Y: at most one of X and Y is
+//true in the pivot, so one of the other two sets is locked and holds their common value Z
+pub fn als_xy_wing(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let grid = Grid::new(candidates, layout);
+
+    //The sets each set is linked to and by which values
+    let mut links: Vec<Vec<(usize, u8)>> = vec![Vec::new(); grid.sets.len()];
+    for (position, als1) in grid.sets.iter().enumerate() {
+        for (offset, als2) in grid.sets[position + 1..].iter().enumerate() {
+            for value in als1.restricted_commons(als2) {
+                links[position].push((position + 1 + offset, value));
+                links[position + 1 + offset].push((position, value));
+            }
+        }
+    }
+
+    for (pivot_idx, pivot) in grid.sets.iter().enumerate() {
+        for (position, &(idx1, x)) in links[pivot_idx].iter().enumerate() {
+            for &(idx2, y) in links[pivot_idx][position + 1..].iter() {
+                let (als1, als2) = (&grid.sets[idx1], &grid.sets[idx2]);
+                if idx1 == idx2 || x == y || !als1.cell_set.is_disjoint(&als2.cell_set) {
+                    continue;
+                }
+                for z in mask_values(als1.mask & als2.mask & !(1 << x) & !(1 << y)) {
+                    let targets = grid.eliminations(&[als1, als2], z);
+                    if !targets.is_empty() {
+                        return Some(als_step(Technique::AlsXyWing, layout, &[als1, pivot, als2], &[x, y], None, z, &targets));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+//A stem cell with a petal set for each of its candidates, every petal cell holding that
+//candidate seeing the stem: whatever the stem holds turns one petal into a locked set, so a value
+//all petals share is in one of them
+pub fn death_blossom(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let grid = Grid::new(candidates, layout);
+
+    for stem in 0..layout.geometry.cell_count() {
+        if candidates.value(stem) != 0 || candidates.count(stem) < 2 || candidates.count(stem) > 3 {
+            continue;
+        }
+        let stem_values = mask_values(candidates.mask(stem));
+
+        for z in (1..layout.geometry.side() as u8 + 1).filter(|value| !candidates.has(stem, *value)) {
+            //The possible petals of every stem value, all of them holding z
+            let petals: Vec<Vec<&Als>> = stem_values.iter().map(|value| {
+                grid.sets.iter()
+                    .filter(|als| als.mask & (1 << z) != 0 && als.mask & (1 << value) != 0)
+                    .filter(|als| als.value_cells[*value as usize].is_subset(&grid.peers[stem]))
+                    .collect()
+            }).collect();
+
+            let mut chosen = Vec::new();
+            if let Some(targets) = choose_petals(&petals, z, &mut chosen, grid.value_cells[z as usize].clone()) {
+                return Some(als_step(Technique::DeathBlossom, layout, &chosen, &stem_values, Some(layout.cell(stem)), z, &targets));
+            }
+        }
+    }
+
+    None
+}
+
+//Picks a petal for every stem value, keeping only the targets that see the z cells of them all
+fn choose_petals<'a>(petals: &[Vec<&'a Als>], z: u8, chosen: &mut Vec<&'a Als>, targets: CellSet) -> Option<CellSet> {
+    if targets.is_empty() {
+        return None;
+    }
+    if chosen.len() == petals.len() {
+        return Some(targets);
+    }
+
+    for petal in petals[chosen.len()].iter() {
+        chosen.push(petal);
+        let found = choose_petals(petals, z, chosen, targets.intersection(&petal.seen_by[z as usize]));
+        if found.is_some() {
+            return found;
+        }
+        chosen.pop();
+    }
+
+    None
+}
+
+fn als_step(technique: Technique, layout: &Layout, sets: &[&Als], restricted_commons: &[u8], stem: Option<Cell>,
+            value: u8, targets: &CellSet) -> Step {
+    let mut step = Step::new(technique);
+    step.eliminations = targets.indices().iter().map(|idx| Candidate::new(layout.cell(*idx), value)).collect();
+    for als in sets.iter() {
+        for idx in als.cells.iter() {
+            let cell = layout.cell(*idx);
+            if !step.cells.contains(&cell) {
+                step.cells.push(cell);
+            }
+        }
+    }
+    step.values.push(value);
+    step.detail = Detail::Als {
+        sets: sets.iter().map(|als| als.describe(layout)).collect(),
+        restricted_commons: restricted_commons.to_vec(),
+        stem,
+    };
+    step
+}
+
+impl fmt::Display for AlmostLockedSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values: Vec<String> = self.values.iter().map(|value| value.to_string()).collect();
+        let cells: Vec<String> = self.cells.iter().map(|cell| cell.to_string()).collect();
+        write!(f, "{} in {} ({})", values.join("/"), cells.join(", "), self.unit)
+    }
+}
@@ -0,0 +1,384 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use candidates::{Candidate, Candidates};
+use logical_solver::{Detail, Layout, Step, Technique};
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub enum LinkKind {
+    //At least one of the two candidates is true
+    Strong,
+    //At most one of the two candidates is true
+    Weak,
+}
+
+/// An alternating sequence of candidates, `links[i]` joining `nodes[i]` and `nodes[i + 1]`.
+/// Displayed in Eureka notation, e.g. `(5)r1c2=(5)r1c7-(5)r3c8=(5)r3c1`.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Chain {
+    pub nodes: Vec<Candidate>,
+    pub links: Vec<LinkKind>,
+}
+
+/// The strong and weak links between the candidates of a grid. Links come from the same four
+/// kinds of constraints as the dancing links solver: a cell holds a single value, and a row,
+/// column or block holds every value once.
+pub struct LinkGraph {
+    stride: usize,
+    strong: Vec<Vec<usize>>,
+    weak: Vec<Vec<usize>>,
+}
+
+impl Chain {
+    pub fn new(start: Candidate) -> Chain {
+        Chain {
+            nodes: vec![start],
+            links: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, link: LinkKind, candidate: Candidate) {
+        self.links.push(link);
+        self.nodes.push(candidate);
+    }
+
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+}
+
+impl LinkGraph {
+    pub fn new(candidates: &Candidates, layout: &Layout) -> LinkGraph {
+        let stride = layout.geometry.side() + 1;
+        let node_count = layout.geometry.cell_count() * stride;
+        let mut strong = vec![Vec::new(); node_count];
+        let mut weak = vec![Vec::new(); node_count];
+
+        //Cell links: the candidates of a cell exclude each other and a bivalue cell holds one of them
+        for idx in 0..layout.geometry.cell_count() {
+            if candidates.value(idx) != 0 {
+                continue;
+            }
+            let values: Vec<u8> = (1..stride as u8).filter(|value| candidates.has(idx, *value)).collect();
+            for value1 in values.iter() {
+                for value2 in values.iter().filter(|value2| *value2 != value1) {
+                    weak[idx * stride + *value1 as usize].push(idx * stride + *value2 as usize);
+                    if values.len() == 2 {
+                        strong[idx * stride + *value1 as usize].push(idx * stride + *value2 as usize);
+                    }
+                }
+            }
+        }
+
+        //Unit links: a value appears once per unit and a conjugate pair holds it in one of its cells
+        for unit_idx in 0..layout.units.len() {
+            for value in 1..stride as u8 {
+                let positions = layout.positions(candidates, unit_idx, value);
+                for idx1 in positions.iter() {
+                    for idx2 in positions.iter().filter(|idx2| *idx2 != idx1) {
+                        weak[idx1 * stride + value as usize].push(idx2 * stride + value as usize);
+                        if positions.len() == 2 {
+                            strong[idx1 * stride + value as usize].push(idx2 * stride + value as usize);
+                        }
+                    }
+                }
+            }
+        }
+
+        //Two cells can share a row or column as well as a block
+        for links in strong.iter_mut().chain(weak.iter_mut()) {
+            links.sort();
+            links.dedup();
+        }
+
+        LinkGraph {
+            stride,
+            strong,
+            weak,
+        }
+    }
+
+    pub fn node(&self, idx: usize, value: u8) -> usize {
+        idx * self.stride + value as usize
+    }
+
+    //The cell index and value of a node
+    pub fn candidate_of(&self, node: usize) -> (usize, u8) {
+        (node / self.stride, (node % self.stride) as u8)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.strong.len()
+    }
+
+    pub fn strong_links(&self, node: usize) -> &[usize] {
+        &self.strong[node]
+    }
+
+    //Every strong link is a weak link as well, so these include the strong links
+    pub fn weak_links(&self, node: usize) -> &[usize] {
+        &self.weak[node]
+    }
+
+    pub fn is_weakly_linked(&self, node1: usize, node2: usize) -> bool {
+        self.weak[node1].binary_search(&node2).is_ok()
+    }
+
+    fn candidate(&self, node: usize, layout: &Layout) -> Candidate {
+        let (idx, value) = self.candidate_of(node);
+        Candidate::new(layout.cell(idx), value)
+    }
+
+    fn chain(&self, nodes: &[usize], links: &[LinkKind], layout: &Layout) -> Chain {
+        let mut chain = Chain::new(self.candidate(nodes[0], layout));
+        for (node, link) in nodes[1..].iter().zip(links.iter()) {
+            chain.push(*link, self.candidate(*node, layout));
+        }
+        chain
+    }
+
+    //Strong and weak links taking turns, starting with a strong one
+    fn alternating_chain(&self, nodes: &[usize], layout: &Layout) -> Chain {
+        let links: Vec<LinkKind> = (1..nodes.len())
+            .map(|position| if position % 2 == 1 { LinkKind::Strong } else { LinkKind::Weak })
+            .collect();
+        self.chain(nodes, &links, layout)
+    }
+}
+
+//A chain of links on a single value, starting and ending with a strong link: one of its ends
+//holds the value so every cell seeing both ends loses it
+pub fn x_chain(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let graph = LinkGraph::new(candidates, layout);
+    let mut best: Option<(Vec<usize>, Vec<usize>)> = None;
+    for value in 1..layout.geometry.side() as u8 + 1 {
+        let max_length = best.as_ref().map(|(nodes, _)| nodes.len());
+        if let Some(found) = shortest_chain(&graph, |node| graph.candidate_of(node).1 == value, max_length) {
+            best = Some(found);
+        }
+    }
+
+    let (nodes, eliminations) = best?;
+    Some(chain_step(Technique::XChain, &graph, layout, graph.alternating_chain(&nodes, layout), &eliminations))
+}
+
+//Like an X-Chain, but links may also switch value within a cell: whatever is true at one end
+//or the other, candidates weakly linked to both ends are false
+pub fn alternating_inference_chain(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let graph = LinkGraph::new(candidates, layout);
+    let (nodes, eliminations) = shortest_chain(&graph, |_| true, None)?;
+    Some(chain_step(Technique::AlternatingInferenceChain, &graph, layout, graph.alternating_chain(&nodes, layout), &eliminations))
+}
+
+//Colors the conjugate pairs of a value alternately: one color holds the value throughout. A
+//color appearing twice in a unit is false, and a candidate seeing both colors is false.
+pub fn simple_coloring(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let graph = LinkGraph::new(candidates, layout);
+    let none = usize::MAX;
+    let mut color = vec![none; graph.node_count()];
+    let mut parent = vec![none; graph.node_count()];
+
+    for value in 1..layout.geometry.side() as u8 + 1 {
+        for start in 0..graph.node_count() {
+            if graph.candidate_of(start).1 != value || color[start] != none || graph.strong_links(start).is_empty() {
+                continue;
+            }
+
+            //Color the whole cluster reachable through conjugate pairs
+            let mut cluster = vec![start];
+            color[start] = 0;
+            let mut next = 0;
+            while next < cluster.len() {
+                let node = cluster[next];
+                next += 1;
+                for linked in graph.strong_links(node).iter().cloned() {
+                    if graph.candidate_of(linked).1 == value && color[linked] == none {
+                        color[linked] = 1 - color[node];
+                        parent[linked] = node;
+                        cluster.push(linked);
+                    }
+                }
+            }
+            if cluster.len() < 3 {
+                continue;
+            }
+
+            //Color wrap
+            for (position, node1) in cluster.iter().enumerate() {
+                for node2 in cluster[position + 1..].iter() {
+                    if color[*node1] != color[*node2] || !graph.is_weakly_linked(*node1, *node2) {
+                        continue;
+                    }
+                    let wrong_color = color[*node1];
+                    let eliminations: Vec<usize> = cluster.iter().cloned().filter(|node| color[*node] == wrong_color).collect();
+
+                    //The path between the two nodes, closed by the weak link between them
+                    let mut nodes = tree_path(&parent, *node1, *node2);
+                    let mut links = vec![LinkKind::Strong; nodes.len() - 1];
+                    nodes.push(*node1);
+                    links.push(LinkKind::Weak);
+                    let chain = graph.chain(&nodes, &links, layout);
+                    return Some(chain_step(Technique::SimpleColoring, &graph, layout, chain, &eliminations));
+                }
+            }
+
+            //Color trap
+            for node1 in cluster.iter().filter(|node| color[**node] == 0) {
+                for node2 in cluster.iter().filter(|node| color[**node] == 1) {
+                    let eliminations: Vec<usize> = graph.weak_links(*node1).iter().cloned()
+                        .filter(|node| !cluster.contains(node) && graph.is_weakly_linked(*node, *node2))
+                        .collect();
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+                    let nodes = tree_path(&parent, *node1, *node2);
+                    let chain = graph.chain(&nodes, &vec![LinkKind::Strong; nodes.len() - 1], layout);
+                    return Some(chain_step(Technique::SimpleColoring, &graph, layout, chain, &eliminations));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+//The shortest alternating chain over the allowed nodes that starts and ends with a strong link and
+//eliminates something, together with its eliminations. Chains as long as max_length are skipped.
+fn shortest_chain<F: Fn(usize) -> bool>(graph: &LinkGraph, allowed: F, max_length: Option<usize>) -> Option<(Vec<usize>, Vec<usize>)> {
+    let none = usize::MAX;
+    let mut best: Option<(Vec<usize>, Vec<usize>)> = None;
+    let mut max_length = max_length.unwrap_or(none);
+
+    //A state is a node together with the kind of link leaving it: odd states continue with a
+    //strong link, even states with a weak one
+    let mut parent = vec![none; graph.node_count() * 2];
+    let mut depth = vec![0; graph.node_count() * 2];
+    let mut visited: Vec<usize> = Vec::new();
+
+    for start in 0..graph.node_count() {
+        if !allowed(start) || graph.strong_links(start).is_empty() {
+            continue;
+        }
+        for state in visited.drain(..) {
+            parent[state] = none;
+        }
+
+        let start_state = start * 2 + 1;
+        parent[start_state] = start_state;
+        depth[start_state] = 0;
+        visited.push(start_state);
+        let mut queue = VecDeque::new();
+        queue.push_back(start_state);
+
+        'search: while let Some(state) = queue.pop_front() {
+            //Nodes in a chain are one more than its links
+            if depth[state] + 2 >= max_length {
+                break;
+            }
+            let node = state / 2;
+            let (links, next_parity) = if state % 2 == 1 {
+                (graph.strong_links(node), 0)
+            } else {
+                (graph.weak_links(node), 1)
+            };
+
+            for linked in links.iter().cloned().filter(|linked| allowed(*linked)) {
+                let next_state = linked * 2 + next_parity;
+                if parent[next_state] != none {
+                    continue;
+                }
+                parent[next_state] = state;
+                depth[next_state] = depth[state] + 1;
+                visited.push(next_state);
+                queue.push_back(next_state);
+
+                if next_parity == 1 || linked == start {
+                    continue;
+                }
+                let eliminations: Vec<usize> = graph.weak_links(start).iter().cloned()
+                    .filter(|node| graph.is_weakly_linked(*node, linked))
+                    .collect();
+                if !eliminations.is_empty() {
+                    let mut nodes = vec![linked];
+                    let mut current = next_state;
+                    while parent[current] != current {
+                        current = parent[current];
+                        nodes.push(current / 2);
+                    }
+                    nodes.reverse();
+                    max_length = nodes.len();
+                    best = Some((nodes, eliminations));
+                    break 'search;
+                }
+            }
+        }
+    }
+
+    best
+}
+
+//The path between two nodes of a coloring tree
+fn tree_path(parent: &[usize], node1: usize, node2: usize) -> Vec<usize> {
+    let ancestors = |mut node: usize| {
+        let mut path = vec![node];
+        while parent[node] != usize::MAX {
+            node = parent[node];
+            path.push(node);
+        }
+        path
+    };
+    let mut path1 = ancestors(node1);
+    let mut path2 = ancestors(node2);
+
+    //Drop the common part above the lowest common ancestor, keeping the ancestor itself once
+    while path1.len() > 1 && path2.len() > 1 && path1[path1.len() - 2] == path2[path2.len() - 2] {
+        path1.pop();
+        path2.pop();
+    }
+    path2.pop();
+    path2.reverse();
+    path1.extend(path2);
+    path1
+}
+
+fn chain_step(technique: Technique, graph: &LinkGraph, layout: &Layout, chain: Chain, eliminations: &[usize]) -> Step {
+    let mut step = Step::new(technique);
+    for candidate in chain.nodes.iter() {
+        if !step.cells.contains(&candidate.cell) {
+            step.cells.push(candidate.cell);
+        }
+        if !step.values.contains(&candidate.value) {
+            step.values.push(candidate.value);
+        }
+    }
+    step.values.sort();
+    step.eliminations = eliminations.iter().map(|node| graph.candidate(*node, layout)).collect();
+    step.eliminations.sort();
+    step.detail = Detail::Chain(chain);
+    step
+}
+
+impl fmt::Display for LinkKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LinkKind::Strong => write!(f, "="),
+            LinkKind::Weak => write!(f, "-"),
+        }
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (position, node) in self.nodes.iter().enumerate() {
+            if position > 0 {
+                write!(f, "{}", self.links[position - 1])?;
+            }
+            write!(f, "({}){}", node.value, node.cell)?;
+        }
+        Ok(())
+    }
+}
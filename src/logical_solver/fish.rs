@@ -0,0 +1,158 @@
+use board::Unit;
+use candidates::{Candidate, Candidates};
+use logical_solver::{combinations, Detail, Layout, Step, Technique};
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum FishKind {
+    Basic,
+    Finned,
+    Sashimi,
+}
+
+fn fish_technique(size: usize, kind: FishKind) -> Technique {
+    match (kind, size) {
+        (FishKind::Basic, 2) => Technique::XWing,
+        (FishKind::Basic, 3) => Technique::Swordfish,
+        (FishKind::Basic, _) => Technique::Jellyfish,
+        (FishKind::Finned, 2) => Technique::FinnedXWing,
+        (FishKind::Finned, 3) => Technique::FinnedSwordfish,
+        (FishKind::Finned, _) => Technique::FinnedJellyfish,
+        (FishKind::Sashimi, 2) => Technique::SashimiXWing,
+        (FishKind::Sashimi, 3) => Technique::SashimiSwordfish,
+        (FishKind::Sashimi, _) => Technique::SashimiJellyfish,
+    }
+}
+
+//Within size base rows (or columns) a value only fits in size cover columns (or rows), so one of
+//the base cells holds the value in each cover line and it can be removed from the rest of the
+//cover lines. A finned fish has extra candidates in the base lines, all within one block: either
+//a fin is true or the fish is, so only cells seeing every fin lose the value.
+pub fn fish(candidates: &Candidates, layout: &Layout, size: usize, kind: FishKind) -> Option<Step> {
+    let geometry = layout.geometry;
+    let side = geometry.side();
+    //Fins have to share a block, which spans at most this many cover lines
+    let max_fin_lines = geometry.box_width.max(geometry.box_height);
+
+    for value in 1..side as u8 + 1 {
+        //Base lines are rows (kind 0) with columns (kind 1) as cover lines, or the other way around
+        for base_kind in 0..2 {
+            let cover_kind = 1 - base_kind;
+
+            let lines: Vec<(usize, Vec<usize>)> = (base_kind * side..(base_kind + 1) * side)
+                .map(|unit_idx| (unit_idx, layout.positions(candidates, unit_idx, value)))
+                .filter(|(_, positions)| {
+                    !positions.is_empty() && (kind != FishKind::Basic || positions.len() <= size)
+                })
+                .collect();
+            let line_ids: Vec<usize> = (0..lines.len()).collect();
+
+            for base in combinations(&line_ids, size) {
+                let base_units: Vec<usize> = base.iter().map(|line| lines[*line].0).collect();
+                let base_cells: Vec<usize> = base.iter().flat_map(|line| lines[*line].1.iter().cloned()).collect();
+
+                let mut touched_lines: Vec<usize> = base_cells.iter().map(|idx| layout.cell_units[*idx][cover_kind]).collect();
+                touched_lines.sort();
+                touched_lines.dedup();
+
+                let cover_choices = match kind {
+                    FishKind::Basic => {
+                        if touched_lines.len() != size {
+                            continue;
+                        }
+                        vec![touched_lines]
+                    },
+                    _ => {
+                        if touched_lines.len() <= size || touched_lines.len() > size + max_fin_lines {
+                            continue;
+                        }
+                        combinations(&touched_lines, size)
+                    },
+                };
+
+                for cover_units in cover_choices {
+                    let step = fish_step(candidates, layout, value, size, kind, base_kind, &base_units, &base_cells, &cover_units);
+                    if step.is_some() {
+                        return step;
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[allow(clippy::too_many_arguments)]
+fn fish_step(candidates: &Candidates, layout: &Layout, value: u8, size: usize, kind: FishKind, base_kind: usize,
+             base_units: &[usize], base_cells: &[usize], cover_units: &[usize]) -> Option<Step> {
+    let cover_kind = 1 - base_kind;
+    let fins: Vec<usize> = base_cells.iter().cloned()
+        .filter(|idx| !cover_units.contains(&layout.cell_units[*idx][cover_kind]))
+        .collect();
+
+    let fin_block = match fins.first() {
+        Some(fin) => {
+            let fin_block = layout.cell_units[*fin][2];
+            if fins.iter().any(|idx| layout.cell_units[*idx][2] != fin_block) {
+                return None;
+            }
+            Some(fin_block)
+        },
+        None => None,
+    };
+    if (kind == FishKind::Basic) != fin_block.is_none() {
+        return None;
+    }
+
+    //Every base line needs a cell in the cover lines and every cover line a cell in the base lines
+    let body_counts: Vec<usize> = base_units.iter().map(|unit_idx| {
+        base_cells.iter().filter(|idx| layout.cell_units[**idx][base_kind] == *unit_idx && !fins.contains(idx)).count()
+    }).collect();
+    if body_counts.contains(&0) {
+        return None;
+    }
+    if cover_units.iter().any(|unit_idx| {
+        !base_cells.iter().any(|idx| layout.cell_units[*idx][cover_kind] == *unit_idx && !fins.contains(idx))
+    }) {
+        return None;
+    }
+
+    //Without its fins a sashimi fish has a base line with a single cell left
+    let is_sashimi = body_counts.iter().any(|count| *count < 2);
+    if kind != FishKind::Basic && (kind == FishKind::Sashimi) != is_sashimi {
+        return None;
+    }
+
+    let mut step = Step::new(fish_technique(size, kind));
+    for unit_idx in cover_units.iter() {
+        for idx in layout.unit_cells[*unit_idx].iter() {
+            if !candidates.has(*idx, value) || base_units.contains(&layout.cell_units[*idx][base_kind]) {
+                continue;
+            }
+            if let Some(fin_block) = fin_block {
+                if layout.cell_units[*idx][2] != fin_block {
+                    continue;
+                }
+            }
+            step.eliminations.push(Candidate::new(layout.cell(*idx), value));
+        }
+    }
+    if step.eliminations.is_empty() {
+        return None;
+    }
+
+    let mut cells: Vec<usize> = base_cells.to_vec();
+    cells.sort();
+    step.cells = cells.iter().map(|idx| layout.cell(*idx)).collect();
+    step.values.push(value);
+    let base: Vec<Unit> = base_units.iter().map(|unit_idx| layout.units[*unit_idx]).collect();
+    let cover: Vec<Unit> = cover_units.iter().map(|unit_idx| layout.units[*unit_idx]).collect();
+    step.units = base.iter().chain(cover.iter()).cloned().collect();
+    step.detail = Detail::Fish {
+        base_units: base,
+        cover_units: cover,
+        fins: fins.iter().map(|idx| layout.cell(*idx)).collect(),
+    };
+
+    Some(step)
+}
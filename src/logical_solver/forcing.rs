@@ -0,0 +1,451 @@
+use std::fmt;
+
+use candidates::{mask_values, Candidate, Candidates};
+use logical_solver::{Detail, Layout, Step, Technique};
+
+const NONE: usize = usize::MAX;
+
+/// How far forcing chains and nets look. Implications further than `max_depth` steps away from
+/// the assumption are not followed, and cells or units with more than `max_branches` candidates
+/// are not used as assumptions since they make for unreadable explanations.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct ForcingLimits {
+    pub max_depth: usize,
+    pub max_branches: usize,
+}
+
+impl Default for ForcingLimits {
+    fn default() -> ForcingLimits {
+        ForcingLimits {
+            max_depth: 12,
+            max_branches: 4,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum ForcingKind {
+    //Every candidate of a cell
+    Cell,
+    //Every position of a value in a unit
+    Unit,
+    //A single candidate being true and being false
+    Digit,
+}
+
+/// A candidate found to be true or false within a branch, following from the implications at the
+/// indices in `reasons`.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Implication {
+    pub candidate: Candidate,
+    pub is_true: bool,
+    pub reasons: Vec<usize>,
+}
+
+/// The implications of one assumption, which is the first of them. The last implication is the
+/// conclusion of the branch, unless the branch ends in a contradiction between the implications
+/// listed in `contradiction`.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Branch {
+    pub implications: Vec<Implication>,
+    pub contradiction: Option<Vec<usize>>,
+}
+
+struct Fact {
+    idx: usize,
+    value: u8,
+    is_true: bool,
+    depth: usize,
+    reasons: Vec<usize>,
+}
+
+//Follows the singles an assumption leads to on a copy of the candidates
+struct Propagation<'a> {
+    layout: &'a Layout,
+    stride: usize,
+    //Nets may combine several implications of the branch to reach the next one, chains may not
+    nets: bool,
+    max_depth: usize,
+    values: Vec<u8>,
+    masks: Vec<u32>,
+    facts: Vec<Fact>,
+    true_facts: Vec<usize>,
+    false_facts: Vec<usize>,
+    contradiction: Option<Vec<usize>>,
+}
+
+impl<'a> Propagation<'a> {
+    fn new(candidates: &Candidates, layout: &'a Layout, nets: bool, max_depth: usize) -> Propagation<'a> {
+        let cell_count = layout.geometry.cell_count();
+        let stride = layout.geometry.side() + 1;
+
+        Propagation {
+            layout,
+            stride,
+            nets,
+            max_depth,
+            values: (0..cell_count).map(|idx| candidates.value(idx)).collect(),
+            masks: (0..cell_count).map(|idx| candidates.mask(idx)).collect(),
+            facts: Vec::new(),
+            true_facts: vec![NONE; cell_count * stride],
+            false_facts: vec![NONE; cell_count * stride],
+            contradiction: None,
+        }
+    }
+
+    fn run(mut self, idx: usize, value: u8, is_true: bool) -> Propagation<'a> {
+        self.add_fact(idx, value, is_true, Vec::new());
+
+        let mut next = 0;
+        while next < self.facts.len() && self.contradiction.is_none() {
+            if self.facts[next].depth < self.max_depth {
+                self.expand(next);
+            }
+            next += 1;
+            //Nets fall back on locked candidates once the singles run out
+            if next == self.facts.len() && self.nets && self.contradiction.is_none() {
+                self.lock_candidates();
+            }
+        }
+
+        self
+    }
+
+    //Eliminates a value from the rest of a line when its positions in a block all lie on the line,
+    //and from the rest of a block when its positions in a line all lie in the block
+    fn lock_candidates(&mut self) {
+        let layout = self.layout;
+        let side = layout.geometry.side();
+        for block_idx in 2 * side..3 * side {
+            //Rows, then columns
+            for kind in 0..2 {
+                let mut lines: Vec<usize> = layout.unit_cells[block_idx].iter()
+                    .map(|idx| layout.cell_units[*idx][kind])
+                    .collect();
+                lines.sort();
+                lines.dedup();
+                for line_idx in lines {
+                    for value in 1..side as u8 + 1 {
+                        self.lock(block_idx, line_idx, value);
+                        self.lock(line_idx, block_idx, value);
+                    }
+                }
+            }
+        }
+    }
+
+    //Rules value out in the rest of target when its positions in source all lie in target
+    fn lock(&mut self, source: usize, target: usize, value: u8) {
+        let layout = self.layout;
+        let source_cells = &layout.unit_cells[source];
+        let target_cells = &layout.unit_cells[target];
+        if source_cells.iter().any(|idx| self.values[*idx] == value) {
+            return;
+        }
+        let (inside, outside): (Vec<usize>, Vec<usize>) = source_cells.iter().cloned()
+            .filter(|idx| self.is_open(*idx, value))
+            .partition(|idx| target_cells.contains(idx));
+        if inside.is_empty() || !outside.is_empty() {
+            return;
+        }
+        let reasons: Vec<usize> = source_cells.iter()
+            .filter(|idx| !target_cells.contains(idx))
+            .map(|idx| self.fact(*idx, value, false))
+            .filter(|reason| *reason != NONE)
+            .collect();
+        if reasons.is_empty() || reasons.iter().any(|reason| self.facts[*reason].depth >= self.max_depth) {
+            return;
+        }
+        for idx in target_cells.iter() {
+            if !source_cells.contains(idx) && self.is_open(*idx, value) {
+                self.add_fact(*idx, value, false, reasons.clone());
+            }
+        }
+    }
+
+    fn is_open(&self, idx: usize, value: u8) -> bool {
+        self.values[idx] == 0 && self.masks[idx] & (1 << value) != 0
+    }
+
+    fn fact(&self, idx: usize, value: u8, is_true: bool) -> usize {
+        let node = idx * self.stride + value as usize;
+        if is_true { self.true_facts[node] } else { self.false_facts[node] }
+    }
+
+    fn add_fact(&mut self, idx: usize, value: u8, is_true: bool, reasons: Vec<usize>) {
+        if self.contradiction.is_some() || self.fact(idx, value, is_true) != NONE {
+            return;
+        }
+
+        let fact_id = self.facts.len();
+        let depth = reasons.iter().map(|reason| self.facts[*reason].depth + 1).max().unwrap_or(0);
+        self.facts.push(Fact { idx, value, is_true, depth, reasons });
+
+        let node = idx * self.stride + value as usize;
+        if is_true {
+            self.true_facts[node] = fact_id;
+            //Already ruled out, or the cell already took another value
+            let opposite = self.fact(idx, value, false);
+            if opposite != NONE {
+                self.contradiction = Some(vec![opposite, fact_id]);
+                return;
+            }
+            if self.values[idx] != 0 {
+                let other = self.fact(idx, self.values[idx], true);
+                self.contradiction = Some(vec![other, fact_id]);
+                return;
+            }
+            self.values[idx] = value;
+        } else {
+            self.false_facts[node] = fact_id;
+            let opposite = self.fact(idx, value, true);
+            if opposite != NONE {
+                self.contradiction = Some(vec![opposite, fact_id]);
+                return;
+            }
+            self.masks[idx] &= !(1 << value);
+        }
+    }
+
+    fn expand(&mut self, fact_id: usize) {
+        let (idx, value) = (self.facts[fact_id].idx, self.facts[fact_id].value);
+        let layout = self.layout;
+
+        if self.facts[fact_id].is_true {
+            //The other candidates of the cell and the value in every peer
+            for other in mask_values(self.masks[idx]) {
+                if other != value {
+                    self.add_fact(idx, other, false, vec![fact_id]);
+                }
+            }
+            for unit_idx in layout.cell_units[idx].iter() {
+                for peer in layout.unit_cells[*unit_idx].iter() {
+                    if *peer != idx && self.values[*peer] == 0 && self.masks[*peer] & (1 << value) != 0 {
+                        self.add_fact(*peer, value, false, vec![fact_id]);
+                    }
+                }
+            }
+            return;
+        }
+
+        //A naked single left in the cell
+        if self.values[idx] == 0 {
+            let reasons: Vec<usize> = (1..self.stride as u8)
+                .map(|other| self.fact(idx, other, false))
+                .filter(|reason| *reason != NONE)
+                .collect();
+            match self.masks[idx].count_ones() {
+                0 => self.contradiction = Some(reasons),
+                1 if self.nets || reasons.len() == 1 => {
+                    let remaining = self.masks[idx].trailing_zeros() as u8;
+                    self.add_fact(idx, remaining, true, reasons);
+                },
+                _ => {},
+            }
+        }
+
+        //A hidden single left in one of the units of the cell
+        for unit_idx in layout.cell_units[idx].iter() {
+            let cells = &layout.unit_cells[*unit_idx];
+            if cells.iter().any(|cell| self.values[*cell] == value) {
+                continue;
+            }
+            let positions: Vec<usize> = cells.iter().cloned()
+                .filter(|cell| self.values[*cell] == 0 && self.masks[*cell] & (1 << value) != 0)
+                .collect();
+            let reasons: Vec<usize> = cells.iter()
+                .map(|cell| self.fact(*cell, value, false))
+                .filter(|reason| *reason != NONE)
+                .collect();
+            match positions.len() {
+                0 => self.contradiction = Some(reasons),
+                1 if self.nets || reasons.len() == 1 => self.add_fact(positions[0], value, true, reasons),
+                _ => {},
+            }
+            if self.contradiction.is_some() {
+                return;
+            }
+        }
+    }
+
+    //The facts leading to the given ones, renumbered in the order they were found
+    fn branch(&self, targets: &[usize], contradiction: bool) -> Branch {
+        let mut needed = vec![false; self.facts.len()];
+        let mut pending: Vec<usize> = targets.to_vec();
+        while let Some(fact_id) = pending.pop() {
+            if !needed[fact_id] {
+                needed[fact_id] = true;
+                pending.extend(self.facts[fact_id].reasons.iter().cloned());
+            }
+        }
+
+        let mut new_ids = vec![NONE; self.facts.len()];
+        let mut implications = Vec::new();
+        for (fact_id, fact) in self.facts.iter().enumerate().filter(|&(fact_id, _)| needed[fact_id]) {
+            new_ids[fact_id] = implications.len();
+            implications.push(Implication {
+                candidate: Candidate::new(self.layout.cell(fact.idx), fact.value),
+                is_true: fact.is_true,
+                reasons: fact.reasons.iter().map(|reason| new_ids[*reason]).collect(),
+            });
+        }
+
+        Branch {
+            implications,
+            contradiction: if contradiction { Some(targets.iter().map(|target| new_ids[*target]).collect()) } else { None },
+        }
+    }
+}
+
+fn forcing_technique(kind: ForcingKind, nets: bool) -> Technique {
+    match (kind, nets) {
+        (ForcingKind::Cell, false) => Technique::CellForcingChain,
+        (ForcingKind::Unit, false) => Technique::UnitForcingChain,
+        (ForcingKind::Digit, false) => Technique::DigitForcingChain,
+        (ForcingKind::Cell, true) => Technique::CellForcingNet,
+        (ForcingKind::Unit, true) => Technique::UnitForcingNet,
+        (ForcingKind::Digit, true) => Technique::DigitForcingNet,
+    }
+}
+
+//Makes each of a set of assumptions, one of which has to be true, and follows their implications.
+//A branch ending in a contradiction shows its assumption to be false, and an implication common
+//to every branch is true whichever assumption is.
+pub fn forcing(candidates: &Candidates, layout: &Layout, kind: ForcingKind, nets: bool, limits: ForcingLimits) -> Option<Step> {
+    for assumptions in assumption_sets(candidates, layout, kind, limits.max_branches) {
+        let propagations: Vec<Propagation> = assumptions.iter()
+            .map(|&(idx, value, is_true)| Propagation::new(candidates, layout, nets, limits.max_depth).run(idx, value, is_true))
+            .collect();
+        let open: Vec<&Propagation> = propagations.iter().filter(|propagation| propagation.contradiction.is_none()).collect();
+        if open.is_empty() {
+            //The candidates were broken to begin with
+            continue;
+        }
+
+        let mut step = Step::new(forcing_technique(kind, nets));
+        if let Some(position) = propagations.iter().position(|propagation| propagation.contradiction.is_some()) {
+            let (idx, value, is_true) = assumptions[position];
+            let conclusion = Candidate::new(layout.cell(idx), value);
+            //A false assumption of a digit forcing chain makes the candidate true
+            if is_true {
+                step.eliminations.push(conclusion);
+            } else {
+                step.placements.push(conclusion);
+            }
+            let propagation = &propagations[position];
+            let branch = propagation.branch(propagation.contradiction.as_ref().unwrap(), true);
+            return Some(forcing_step(step, layout, &assumptions, vec![branch]));
+        }
+
+        //The common implication reached with the fewest steps
+        let mut best: Option<(usize, usize)> = None;
+        for (fact_id, fact) in open[0].facts.iter().enumerate().skip(1) {
+            let depths: Option<Vec<usize>> = open.iter()
+                .map(|propagation| {
+                    let other = propagation.fact(fact.idx, fact.value, fact.is_true);
+                    if other == NONE { None } else { Some(propagation.facts[other].depth) }
+                })
+                .collect();
+            if let Some(depths) = depths {
+                let total: usize = depths.iter().sum();
+                let is_better = match best {
+                    Some((_, best_total)) => total < best_total,
+                    None => true,
+                };
+                if is_better {
+                    best = Some((fact_id, total));
+                }
+            }
+        }
+
+        if let Some((fact_id, _)) = best {
+            let fact = &open[0].facts[fact_id];
+            let conclusion = Candidate::new(layout.cell(fact.idx), fact.value);
+            if fact.is_true {
+                step.placements.push(conclusion);
+            } else {
+                step.eliminations.push(conclusion);
+            }
+            let branches = open.iter()
+                .map(|propagation| propagation.branch(&[propagation.fact(fact.idx, fact.value, fact.is_true)], false))
+                .collect();
+            return Some(forcing_step(step, layout, &assumptions, branches));
+        }
+    }
+
+    None
+}
+
+//The sets of assumptions to try, simplest first
+fn assumption_sets(candidates: &Candidates, layout: &Layout, kind: ForcingKind, max_branches: usize) -> Vec<Vec<(usize, u8, bool)>> {
+    let side = layout.geometry.side();
+    let mut sets = Vec::new();
+
+    match kind {
+        ForcingKind::Cell => {
+            for count in 2..max_branches + 1 {
+                for idx in 0..layout.geometry.cell_count() {
+                    if candidates.value(idx) == 0 && candidates.count(idx) as usize == count {
+                        sets.push(mask_values(candidates.mask(idx)).into_iter().map(|value| (idx, value, true)).collect());
+                    }
+                }
+            }
+        },
+        ForcingKind::Unit => {
+            for count in 2..max_branches + 1 {
+                for unit_idx in 0..layout.units.len() {
+                    for value in 1..side as u8 + 1 {
+                        let positions = layout.positions(candidates, unit_idx, value);
+                        if positions.len() == count {
+                            sets.push(positions.into_iter().map(|idx| (idx, value, true)).collect());
+                        }
+                    }
+                }
+            }
+        },
+        ForcingKind::Digit => {
+            for idx in 0..layout.geometry.cell_count() {
+                if candidates.value(idx) != 0 {
+                    continue;
+                }
+                for value in mask_values(candidates.mask(idx)) {
+                    sets.push(vec![(idx, value, true), (idx, value, false)]);
+                }
+            }
+        },
+    }
+
+    sets
+}
+
+fn forcing_step(mut step: Step, layout: &Layout, assumptions: &[(usize, u8, bool)], branches: Vec<Branch>) -> Step {
+    for &(idx, value, _) in assumptions.iter() {
+        let cell = layout.cell(idx);
+        if !step.cells.contains(&cell) {
+            step.cells.push(cell);
+        }
+        if !step.values.contains(&value) {
+            step.values.push(value);
+        }
+    }
+    step.detail = Detail::Forcing(branches);
+    step
+}
+
+impl fmt::Display for Implication {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let relation = if self.is_true { "=" } else { "<>" };
+        write!(f, "{}{}{}", self.candidate.cell, relation, self.candidate.value)
+    }
+}
+
+impl fmt::Display for Branch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let implications: Vec<String> = self.implications.iter().map(|implication| implication.to_string()).collect();
+        write!(f, "[{}", implications.join(" -> "))?;
+        if self.contradiction.is_some() {
+            write!(f, " -> contradiction")?;
+        }
+        write!(f, "]")
+    }
+}
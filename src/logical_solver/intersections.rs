@@ -0,0 +1,80 @@
+use candidates::{Candidate, Candidates};
+use logical_solver::{Layout, Step, Technique};
+
+//The candidates of a value within a block all lie on one row or column, so the value can be
+//removed from the rest of that line
+pub fn pointing(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let side = layout.geometry.side();
+
+    for block_idx in 2 * side..3 * side {
+        for value in 1..side as u8 + 1 {
+            let positions = layout.positions(candidates, block_idx, value);
+            //A single position is a hidden single
+            if positions.len() < 2 {
+                continue;
+            }
+
+            //Try the row, then the column shared by every position
+            for kind in 0..2 {
+                let line_idx = layout.cell_units[positions[0]][kind];
+                if positions.iter().any(|idx| layout.cell_units[*idx][kind] != line_idx) {
+                    continue;
+                }
+                let step = locked_step(Technique::PointingCandidates, candidates, layout, value, &positions,
+                                       block_idx, line_idx);
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+//The candidates of a value within a row or column all lie in one block, so the value can be
+//removed from the rest of that block
+pub fn claiming(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let side = layout.geometry.side();
+
+    for line_idx in 0..2 * side {
+        for value in 1..side as u8 + 1 {
+            let positions = layout.positions(candidates, line_idx, value);
+            if positions.len() < 2 {
+                continue;
+            }
+
+            let block_idx = layout.cell_units[positions[0]][2];
+            if positions.iter().any(|idx| layout.cell_units[*idx][2] != block_idx) {
+                continue;
+            }
+            let step = locked_step(Technique::ClaimingCandidates, candidates, layout, value, &positions,
+                                   line_idx, block_idx);
+            if step.is_some() {
+                return step;
+            }
+        }
+    }
+
+    None
+}
+
+//Removes value from the cells of target_unit outside of the locked positions
+fn locked_step(technique: Technique, candidates: &Candidates, layout: &Layout, value: u8, positions: &[usize],
+               source_unit: usize, target_unit: usize) -> Option<Step> {
+    let mut step = Step::new(technique);
+    for idx in layout.unit_cells[target_unit].iter() {
+        if !positions.contains(idx) && candidates.has(*idx, value) {
+            step.eliminations.push(Candidate::new(layout.cell(*idx), value));
+        }
+    }
+    if step.eliminations.is_empty() {
+        return None;
+    }
+
+    step.cells = positions.iter().map(|idx| layout.cell(*idx)).collect();
+    step.values.push(value);
+    step.units.push(layout.units[source_unit]);
+    step.units.push(layout.units[target_unit]);
+    Some(step)
+}
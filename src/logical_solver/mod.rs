@@ -0,0 +1,678 @@
+//! Human style solving: deductions a person could follow, reported one step at a time.
+//!
+//! ```
+//! use sudoku_solver::Board;
+//! use sudoku_solver::logical_solver::{LogicalSolver, SolveStatus};
+//!
+//! let board = Board::new("200005709900070000070001050008200000067000520000008100040300080000060002609800003").unwrap();
+//! let solution = LogicalSolver::new().solve(&board);
+//! assert_eq!(solution.status, SolveStatus::Solved);
+//! for step in solution.steps.iter() {
+//!     println!("{}", step);
+//! }
+//! ```
+mod singles;
+mod intersections;
+mod subsets;
+mod fish;
+mod wings;
+mod chains;
+mod uniqueness;
+mod als;
+mod forcing;
+
+use std::fmt;
+
+use board::{Board, Cell, Geometry, Unit};
+use candidates::{Candidate, Candidates};
+use sudoku_solver_dancinglinks::{has_unique_solution, solve_sudoku};
+
+pub use self::als::AlmostLockedSet;
+pub use self::chains::{Chain, LinkGraph, LinkKind};
+pub use self::forcing::{Branch, ForcingLimits, Implication};
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub enum Technique {
+    HiddenSingle,
+    NakedSingle,
+    //A value confined to one row or column of a block is removed from the rest of that line
+    PointingCandidates,
+    //A value confined to one block within a row or column is removed from the rest of that block
+    ClaimingCandidates,
+    NakedPair,
+    HiddenPair,
+    NakedTriple,
+    HiddenTriple,
+    NakedQuad,
+    HiddenQuad,
+    //Fish of size 2, 3 and 4 on a single value
+    XWing,
+    Swordfish,
+    Jellyfish,
+    //Fish with extra candidates (fins) in one block of the base lines
+    FinnedXWing,
+    FinnedSwordfish,
+    FinnedJellyfish,
+    //Finned fish that would be degenerate without their fins
+    SashimiXWing,
+    SashimiSwordfish,
+    SashimiJellyfish,
+    //Bivalue cells whose candidates force one of two pincers to hold a value
+    XYWing,
+    XYZWing,
+    WWing,
+    //Conjugate pairs of a value colored alternately
+    SimpleColoring,
+    //Chains of strong and weak links on one value, then on any candidates
+    XChain,
+    AlternatingInferenceChain,
+    //Patterns that would allow a second solution, only valid for puzzles with a unique solution
+    UniqueRectangleType1,
+    UniqueRectangleType2,
+    UniqueRectangleType3,
+    UniqueRectangleType4,
+    UniqueRectangleType5,
+    UniqueRectangleType6,
+    BugPlusOne,
+    //Almost locked sets linked by restricted common values
+    AlsXz,
+    AlsXyWing,
+    DeathBlossom,
+    //Following the singles that each of a set of assumptions leads to, one implication at a time
+    //for chains and combining implications for nets. Nets also follow locked candidates but never
+    //nest assumptions, so the hardest puzzles such as Easter Monster, Escargot or Inkala's 2012
+    //puzzle stay stuck whatever the ForcingLimits
+    CellForcingChain,
+    UnitForcingChain,
+    DigitForcingChain,
+    CellForcingNet,
+    UnitForcingNet,
+    DigitForcingNet,
+    //Not a deduction: the remaining cells were filled in by the dancing links solver
+    BruteForce,
+}
+
+//The techniques tried by default, simplest first
+pub const BASIC_TECHNIQUES: [Technique; 10] = [
+    Technique::HiddenSingle,
+    Technique::NakedSingle,
+    Technique::PointingCandidates,
+    Technique::ClaimingCandidates,
+    Technique::NakedPair,
+    Technique::HiddenPair,
+    Technique::NakedTriple,
+    Technique::HiddenTriple,
+    Technique::NakedQuad,
+    Technique::HiddenQuad,
+];
+
+pub const FISH_TECHNIQUES: [Technique; 9] = [
+    Technique::XWing,
+    Technique::Swordfish,
+    Technique::Jellyfish,
+    Technique::FinnedXWing,
+    Technique::SashimiXWing,
+    Technique::FinnedSwordfish,
+    Technique::SashimiSwordfish,
+    Technique::FinnedJellyfish,
+    Technique::SashimiJellyfish,
+];
+
+pub const CHAIN_TECHNIQUES: [Technique; 6] = [
+    Technique::XYWing,
+    Technique::XYZWing,
+    Technique::WWing,
+    Technique::SimpleColoring,
+    Technique::XChain,
+    Technique::AlternatingInferenceChain,
+];
+
+//Only used by solvers that assume uniqueness
+pub const UNIQUENESS_TECHNIQUES: [Technique; 7] = [
+    Technique::UniqueRectangleType1,
+    Technique::UniqueRectangleType2,
+    Technique::UniqueRectangleType3,
+    Technique::UniqueRectangleType4,
+    Technique::UniqueRectangleType5,
+    Technique::UniqueRectangleType6,
+    Technique::BugPlusOne,
+];
+
+pub const ALS_TECHNIQUES: [Technique; 3] = [
+    Technique::AlsXz,
+    Technique::AlsXyWing,
+    Technique::DeathBlossom,
+];
+
+//The last resort before brute force
+pub const FORCING_TECHNIQUES: [Technique; 6] = [
+    Technique::CellForcingChain,
+    Technique::UnitForcingChain,
+    Technique::DigitForcingChain,
+    Technique::CellForcingNet,
+    Technique::UnitForcingNet,
+    Technique::DigitForcingNet,
+];
+
+//A single deduction together with everything needed to explain it
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Step {
+    pub technique: Technique,
+    //Values the step places
+    pub placements: Vec<Candidate>,
+    //Candidates the step removes
+    pub eliminations: Vec<Candidate>,
+    //Cells, values and units forming the pattern the deduction is based on
+    pub cells: Vec<Cell>,
+    pub values: Vec<u8>,
+    pub units: Vec<Unit>,
+    pub detail: Detail,
+}
+
+//The structure of the more involved patterns, for display
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Detail {
+    None,
+    //The value is confined to the cover lines within the base lines, apart from the fins
+    Fish { base_units: Vec<Unit>, cover_units: Vec<Unit>, fins: Vec<Cell> },
+    //The chain of links the eliminations follow from
+    Chain(Chain),
+    //Almost locked sets and the restricted commons between them, or the stem values of a Death
+    //Blossom linking the stem to its petals
+    Als { sets: Vec<AlmostLockedSet>, restricted_commons: Vec<u8>, stem: Option<Cell> },
+    //What each assumption leads to
+    Forcing(Vec<Branch>),
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum SolveStatus {
+    Solved,
+    //None of the techniques apply anymore
+    Stuck,
+    //The givens or the current candidates cannot lead to a solution
+    Contradiction,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct LogicalSolution {
+    pub steps: Vec<Step>,
+    //The board as far as the steps got
+    pub board: Board,
+    pub status: SolveStatus,
+}
+
+pub struct LogicalSolver {
+    techniques: Vec<Technique>,
+    brute_force_fallback: bool,
+    assume_uniqueness: bool,
+    forcing_limits: ForcingLimits,
+}
+
+//The cells of every unit and the units of every cell of a geometry, computed once per search
+pub struct Layout {
+    pub geometry: Geometry,
+    //Rows, then columns, then blocks
+    pub units: Vec<Unit>,
+    pub unit_cells: Vec<Vec<usize>>,
+    //Indices into units of the row, column and block of every cell
+    pub cell_units: Vec<[usize; 3]>,
+}
+
+impl Technique {
+    //Whether the technique is only valid for puzzles with a unique solution
+    pub fn requires_uniqueness(&self) -> bool {
+        UNIQUENESS_TECHNIQUES.contains(self)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::NakedSingle => "Naked Single",
+            Technique::PointingCandidates => "Pointing Candidates",
+            Technique::ClaimingCandidates => "Claiming Candidates",
+            Technique::NakedPair => "Naked Pair",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::FinnedXWing => "Finned X-Wing",
+            Technique::FinnedSwordfish => "Finned Swordfish",
+            Technique::FinnedJellyfish => "Finned Jellyfish",
+            Technique::SashimiXWing => "Sashimi X-Wing",
+            Technique::SashimiSwordfish => "Sashimi Swordfish",
+            Technique::SashimiJellyfish => "Sashimi Jellyfish",
+            Technique::XYWing => "XY-Wing",
+            Technique::XYZWing => "XYZ-Wing",
+            Technique::WWing => "W-Wing",
+            Technique::SimpleColoring => "Simple Coloring",
+            Technique::XChain => "X-Chain",
+            Technique::AlternatingInferenceChain => "Alternating Inference Chain",
+            Technique::UniqueRectangleType1 => "Unique Rectangle Type 1",
+            Technique::UniqueRectangleType2 => "Unique Rectangle Type 2",
+            Technique::UniqueRectangleType3 => "Unique Rectangle Type 3",
+            Technique::UniqueRectangleType4 => "Unique Rectangle Type 4",
+            Technique::UniqueRectangleType5 => "Unique Rectangle Type 5",
+            Technique::UniqueRectangleType6 => "Unique Rectangle Type 6",
+            Technique::BugPlusOne => "BUG+1",
+            Technique::AlsXz => "ALS-XZ",
+            Technique::AlsXyWing => "ALS-XY-Wing",
+            Technique::DeathBlossom => "Death Blossom",
+            Technique::CellForcingChain => "Cell Forcing Chain",
+            Technique::UnitForcingChain => "Unit Forcing Chain",
+            Technique::DigitForcingChain => "Digit Forcing Chain",
+            Technique::CellForcingNet => "Cell Forcing Net",
+            Technique::UnitForcingNet => "Unit Forcing Net",
+            Technique::DigitForcingNet => "Digit Forcing Net",
+            Technique::BruteForce => "Brute Force",
+        }
+    }
+}
+
+impl Step {
+    pub fn new(technique: Technique) -> Step {
+        Step {
+            technique,
+            placements: Vec::new(),
+            eliminations: Vec::new(),
+            cells: Vec::new(),
+            values: Vec::new(),
+            units: Vec::new(),
+            detail: Detail::None,
+        }
+    }
+
+    /// Applies the placements and eliminations of the step to the candidates.
+    pub fn apply(&self, candidates: &mut Candidates) {
+        let geometry = candidates.geometry();
+        for placement in self.placements.iter() {
+            candidates.place(geometry.index_of(placement.cell), placement.value);
+        }
+        for elimination in self.eliminations.iter() {
+            candidates.remove(geometry.index_of(elimination.cell), elimination.value);
+        }
+    }
+
+    //Whether the step makes any progress at all
+    pub fn is_productive(&self) -> bool {
+        !self.placements.is_empty() || !self.eliminations.is_empty()
+    }
+}
+
+impl Default for LogicalSolver {
+    fn default() -> LogicalSolver {
+        LogicalSolver::new()
+    }
+}
+
+impl LogicalSolver {
+    /// A solver using every technique without falling back to brute force. The uniqueness
+    /// techniques are skipped unless `assume_uniqueness` is enabled.
+    pub fn new() -> LogicalSolver {
+        let techniques: Vec<Technique> = BASIC_TECHNIQUES.iter()
+            .chain(FISH_TECHNIQUES.iter())
+            .chain(UNIQUENESS_TECHNIQUES.iter())
+            .chain(CHAIN_TECHNIQUES.iter())
+            .chain(ALS_TECHNIQUES.iter())
+            .chain(FORCING_TECHNIQUES.iter())
+            .cloned()
+            .collect();
+        LogicalSolver::with_techniques(&techniques)
+    }
+
+    /// A solver trying exactly the given techniques in the given order.
+    pub fn with_techniques(techniques: &[Technique]) -> LogicalSolver {
+        LogicalSolver {
+            techniques: techniques.to_vec(),
+            brute_force_fallback: false,
+            assume_uniqueness: false,
+            forcing_limits: ForcingLimits::default(),
+        }
+    }
+
+    /// When enabled, a board the techniques get stuck on is finished with `solve_sudoku` and
+    /// reported as a single `Technique::BruteForce` step.
+    pub fn brute_force_fallback(mut self, enabled: bool) -> LogicalSolver {
+        self.brute_force_fallback = enabled;
+        self
+    }
+
+    /// When enabled, the uniqueness techniques may be used on boards the dancing links solver
+    /// confirms to have exactly one solution. They are never used on any other board, where they
+    /// could remove the actual solutions.
+    pub fn assume_uniqueness(mut self, enabled: bool) -> LogicalSolver {
+        self.assume_uniqueness = enabled;
+        self
+    }
+
+    /// How far the forcing chains and nets look, 12 implications deep and up to 4 assumptions by
+    /// default. Larger limits find more steps at the cost of slower searches and longer explanations.
+    pub fn forcing_limits(mut self, limits: ForcingLimits) -> LogicalSolver {
+        self.forcing_limits = limits;
+        self
+    }
+
+    pub fn techniques(&self) -> &[Technique] {
+        &self.techniques
+    }
+
+    /// Finds the first applicable step of the simplest technique, without ever brute forcing.
+    pub fn next_step(&self, candidates: &Candidates) -> Option<Step> {
+        let layout = Layout::new(candidates.geometry());
+        self.next_step_with_layout(candidates, &layout, self.uniqueness_allowed(candidates))
+    }
+
+    fn next_step_with_layout(&self, candidates: &Candidates, layout: &Layout, uniqueness: bool) -> Option<Step> {
+        for technique in self.techniques.iter() {
+            if technique.requires_uniqueness() && !uniqueness {
+                continue;
+            }
+            if let Some(step) = find_step(*technique, candidates, layout, self.forcing_limits) {
+                return Some(step);
+            }
+        }
+
+        None
+    }
+
+    /// Solves the board step by step starting from the candidates of its givens.
+    pub fn solve(&self, board: &Board) -> LogicalSolution {
+        self.solve_candidates(Candidates::from_board(board))
+    }
+
+    /// Solves the board step by step starting from the given pencil marks.
+    pub fn solve_candidates(&self, candidates: Candidates) -> LogicalSolution {
+        self.solve_candidates_while(candidates, |_| true)
+    }
+
+    /// Solves like `solve_candidates` but gives up as stuck right after the first step that
+    /// `keep_going` rejects. That step is still applied and reported.
+    pub fn solve_candidates_while<F>(&self, mut candidates: Candidates, mut keep_going: F) -> LogicalSolution
+        where F: FnMut(&Step) -> bool {
+        let layout = Layout::new(candidates.geometry());
+        let uniqueness = self.uniqueness_allowed(&candidates);
+        let mut steps = Vec::new();
+
+        let status = loop {
+            if has_contradiction(&candidates, &layout) {
+                break SolveStatus::Contradiction;
+            }
+            if candidates.is_solved() {
+                break SolveStatus::Solved;
+            }
+
+            let step = match self.next_step_with_layout(&candidates, &layout, uniqueness) {
+                Some(step) => step,
+                None => {
+                    if !self.brute_force_fallback {
+                        break SolveStatus::Stuck;
+                    }
+                    match brute_force(&candidates) {
+                        Some(step) => step,
+                        None => break SolveStatus::Contradiction,
+                    }
+                },
+            };
+
+            step.apply(&mut candidates);
+            let is_accepted = keep_going(&step);
+            steps.push(step);
+            if !is_accepted {
+                break SolveStatus::Stuck;
+            }
+        };
+
+        LogicalSolution {
+            steps,
+            board: candidates.to_board(),
+            status,
+        }
+    }
+
+    //Deductions keep the solutions of the board, so checking the board the candidates were derived
+    //from is enough for every step of the solve
+    fn uniqueness_allowed(&self, candidates: &Candidates) -> bool {
+        self.assume_uniqueness && has_unique_solution(&candidates.to_board())
+    }
+}
+
+impl Layout {
+    pub fn new(geometry: Geometry) -> Layout {
+        let units = geometry.units();
+        let unit_cells: Vec<Vec<usize>> = units.iter().map(|unit| geometry.unit_cells(*unit)).collect();
+
+        let mut cell_units = vec![[0; 3]; geometry.cell_count()];
+        for (unit_idx, cells) in unit_cells.iter().enumerate() {
+            //Units are ordered rows, columns then blocks which matches the order of cell_units
+            let kind = unit_idx / geometry.side();
+            for idx in cells.iter() {
+                cell_units[*idx][kind] = unit_idx;
+            }
+        }
+
+        Layout {
+            geometry,
+            units,
+            unit_cells,
+            cell_units,
+        }
+    }
+
+    pub fn cell(&self, idx: usize) -> Cell {
+        self.geometry.cell_at(idx)
+    }
+
+    //Whether two different cells share a row, column or block
+    pub fn sees(&self, idx1: usize, idx2: usize) -> bool {
+        let (units1, units2) = (self.cell_units[idx1], self.cell_units[idx2]);
+        idx1 != idx2 && (units1[0] == units2[0] || units1[1] == units2[1] || units1[2] == units2[2])
+    }
+
+    //The open cells of a unit that still have value as a candidate
+    pub fn positions(&self, candidates: &Candidates, unit_idx: usize, value: u8) -> Vec<usize> {
+        self.unit_cells[unit_idx].iter().cloned().filter(|idx| candidates.has(*idx, value)).collect()
+    }
+
+    //Whether value has already been placed in the unit
+    pub fn is_placed(&self, candidates: &Candidates, unit_idx: usize, value: u8) -> bool {
+        self.unit_cells[unit_idx].iter().any(|idx| candidates.value(*idx) == value)
+    }
+}
+
+fn find_step(technique: Technique, candidates: &Candidates, layout: &Layout, limits: ForcingLimits) -> Option<Step> {
+    match technique {
+        Technique::HiddenSingle => singles::hidden_single(candidates, layout),
+        Technique::NakedSingle => singles::naked_single(candidates, layout),
+        Technique::PointingCandidates => intersections::pointing(candidates, layout),
+        Technique::ClaimingCandidates => intersections::claiming(candidates, layout),
+        Technique::NakedPair => subsets::naked_subset(candidates, layout, 2),
+        Technique::HiddenPair => subsets::hidden_subset(candidates, layout, 2),
+        Technique::NakedTriple => subsets::naked_subset(candidates, layout, 3),
+        Technique::HiddenTriple => subsets::hidden_subset(candidates, layout, 3),
+        Technique::NakedQuad => subsets::naked_subset(candidates, layout, 4),
+        Technique::HiddenQuad => subsets::hidden_subset(candidates, layout, 4),
+        Technique::XWing => fish::fish(candidates, layout, 2, fish::FishKind::Basic),
+        Technique::Swordfish => fish::fish(candidates, layout, 3, fish::FishKind::Basic),
+        Technique::Jellyfish => fish::fish(candidates, layout, 4, fish::FishKind::Basic),
+        Technique::FinnedXWing => fish::fish(candidates, layout, 2, fish::FishKind::Finned),
+        Technique::FinnedSwordfish => fish::fish(candidates, layout, 3, fish::FishKind::Finned),
+        Technique::FinnedJellyfish => fish::fish(candidates, layout, 4, fish::FishKind::Finned),
+        Technique::SashimiXWing => fish::fish(candidates, layout, 2, fish::FishKind::Sashimi),
+        Technique::SashimiSwordfish => fish::fish(candidates, layout, 3, fish::FishKind::Sashimi),
+        Technique::SashimiJellyfish => fish::fish(candidates, layout, 4, fish::FishKind::Sashimi),
+        Technique::XYWing => wings::xy_wing(candidates, layout),
+        Technique::XYZWing => wings::xyz_wing(candidates, layout),
+        Technique::WWing => wings::w_wing(candidates, layout),
+        Technique::SimpleColoring => chains::simple_coloring(candidates, layout),
+        Technique::XChain => chains::x_chain(candidates, layout),
+        Technique::AlternatingInferenceChain => chains::alternating_inference_chain(candidates, layout),
+        Technique::UniqueRectangleType1 => uniqueness::unique_rectangle(candidates, layout, 1),
+        Technique::UniqueRectangleType2 => uniqueness::unique_rectangle(candidates, layout, 2),
+        Technique::UniqueRectangleType3 => uniqueness::unique_rectangle(candidates, layout, 3),
+        Technique::UniqueRectangleType4 => uniqueness::unique_rectangle(candidates, layout, 4),
+        Technique::UniqueRectangleType5 => uniqueness::unique_rectangle(candidates, layout, 5),
+        Technique::UniqueRectangleType6 => uniqueness::unique_rectangle(candidates, layout, 6),
+        Technique::BugPlusOne => uniqueness::bug_plus_one(candidates, layout),
+        Technique::AlsXz => als::als_xz(candidates, layout),
+        Technique::AlsXyWing => als::als_xy_wing(candidates, layout),
+        Technique::DeathBlossom => als::death_blossom(candidates, layout),
+        Technique::CellForcingChain => forcing::forcing(candidates, layout, forcing::ForcingKind::Cell, false, limits),
+        Technique::UnitForcingChain => forcing::forcing(candidates, layout, forcing::ForcingKind::Unit, false, limits),
+        Technique::DigitForcingChain => forcing::forcing(candidates, layout, forcing::ForcingKind::Digit, false, limits),
+        Technique::CellForcingNet => forcing::forcing(candidates, layout, forcing::ForcingKind::Cell, true, limits),
+        Technique::UnitForcingNet => forcing::forcing(candidates, layout, forcing::ForcingKind::Unit, true, limits),
+        Technique::DigitForcingNet => forcing::forcing(candidates, layout, forcing::ForcingKind::Digit, true, limits),
+        Technique::BruteForce => None,
+    }
+}
+
+//Fills every open cell with the values of the dancing links solution
+fn brute_force(candidates: &Candidates) -> Option<Step> {
+    let solution = solve_sudoku(&candidates.to_board()).ok()?;
+    let geometry = candidates.geometry();
+
+    let mut step = Step::new(Technique::BruteForce);
+    for (idx, val) in solution.cells().iter().enumerate() {
+        if candidates.value(idx) == 0 {
+            //The solution has to agree with the deductions made so far
+            if !candidates.has(idx, *val) {
+                return None;
+            }
+            step.placements.push(Candidate::new(geometry.cell_at(idx), *val));
+        }
+    }
+
+    Some(step)
+}
+
+/// Whether the candidates can no longer lead to a solution: an open cell without candidates, a
+/// value with no place left in a unit, or a value placed twice in a unit.
+pub fn has_contradiction(candidates: &Candidates, layout: &Layout) -> bool {
+    let side = layout.geometry.side();
+    for idx in 0..layout.geometry.cell_count() {
+        if candidates.value(idx) == 0 && candidates.mask(idx) == 0 {
+            return true;
+        }
+    }
+
+    for cells in layout.unit_cells.iter() {
+        let mut placed = 0u32;
+        let mut possible = 0u32;
+        for idx in cells.iter() {
+            let value = candidates.value(*idx);
+            if value != 0 {
+                if placed & (1 << value) != 0 {
+                    return true;
+                }
+                placed |= 1 << value;
+            }
+            possible |= candidates.mask(*idx);
+        }
+        if (placed | possible).count_ones() as usize != side {
+            return true;
+        }
+    }
+
+    false
+}
+
+//Every way of choosing size items, keeping their order
+pub fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    if items.len() < size {
+        return Vec::new();
+    }
+
+    let mut result = Vec::new();
+    for (idx, first) in items.iter().enumerate() {
+        for mut rest in combinations(&items[idx + 1..], size - 1) {
+            rest.insert(0, *first);
+            result.push(rest);
+        }
+    }
+
+    result
+}
+
+//Writes a list of items separated by commas
+fn write_list<T: fmt::Display>(f: &mut fmt::Formatter, items: &[T]) -> fmt::Result {
+    for (idx, item) in items.iter().enumerate() {
+        if idx > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+//Steps are printed as e.g. "Naked Pair: 3/7 in r1c2, r1c5 (row 1) => r1c8<>3, r1c8<>7"
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:", self.technique)?;
+        //Chains and sets name their own candidates
+        let is_chain = matches!(self.detail, Detail::Chain(_) | Detail::Als { .. } | Detail::Forcing(_));
+        if !self.values.is_empty() && !is_chain {
+            let values: Vec<String> = self.values.iter().map(|value| value.to_string()).collect();
+            write!(f, " {}", values.join("/"))?;
+        }
+        if !self.cells.is_empty() && !is_chain {
+            write!(f, " in ")?;
+            write_list(f, &self.cells)?;
+        }
+        match self.detail {
+            Detail::Fish { ref base_units, ref cover_units, ref fins } => {
+                write!(f, " (")?;
+                write_list(f, base_units)?;
+                write!(f, " / ")?;
+                write_list(f, cover_units)?;
+                write!(f, ")")?;
+                if !fins.is_empty() {
+                    write!(f, " fins ")?;
+                    write_list(f, fins)?;
+                }
+            },
+            Detail::Chain(ref chain) => {
+                write!(f, " {}", chain)?;
+            },
+            Detail::Als { ref sets, ref restricted_commons, ref stem } => {
+                let commons: Vec<String> = restricted_commons.iter().map(|value| value.to_string()).collect();
+                match *stem {
+                    Some(stem) => write!(f, " stem {} {}, petals ", stem, commons.join("/"))?,
+                    None => write!(f, " ")?,
+                }
+                let sets: Vec<String> = sets.iter().map(|set| set.to_string()).collect();
+                write!(f, "{}", sets.join(" + "))?;
+                if stem.is_none() {
+                    write!(f, ", restricted common {}", commons.join("/"))?;
+                }
+            },
+            Detail::Forcing(ref branches) => {
+                let branches: Vec<String> = branches.iter().map(|branch| branch.to_string()).collect();
+                write!(f, " {}", branches.join(" | "))?;
+            },
+            Detail::None => {
+                if !self.units.is_empty() {
+                    write!(f, " (")?;
+                    write_list(f, &self.units)?;
+                    write!(f, ")")?;
+                }
+            },
+        }
+        write!(f, " =>")?;
+
+        let mut results: Vec<String> = self.placements.iter().map(|placement| placement.to_string()).collect();
+        results.extend(self.eliminations.iter().map(|elimination| format!("{}<>{}", elimination.cell, elimination.value)));
+        write!(f, " {}", results.join(", "))
+    }
+}
@@ -0,0 +1,46 @@
+use candidates::{Candidate, Candidates};
+use logical_solver::{Layout, Step, Technique};
+
+//An open cell with a single candidate left
+pub fn naked_single(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    for idx in 0..layout.geometry.cell_count() {
+        if candidates.value(idx) == 0 && candidates.count(idx) == 1 {
+            let value = candidates.mask(idx).trailing_zeros() as u8;
+            let cell = layout.cell(idx);
+
+            let mut step = Step::new(Technique::NakedSingle);
+            step.placements.push(Candidate::new(cell, value));
+            step.cells.push(cell);
+            step.values.push(value);
+            return Some(step);
+        }
+    }
+
+    None
+}
+
+//A value that fits in a single cell of a unit. Blocks are searched first since they are the
+//easiest for a person to spot.
+pub fn hidden_single(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let side = layout.geometry.side();
+    let unit_order = (2 * side..3 * side).chain(0..2 * side);
+
+    for unit_idx in unit_order {
+        for value in 1..side as u8 + 1 {
+            let positions = layout.positions(candidates, unit_idx, value);
+            if positions.len() != 1 {
+                continue;
+            }
+            let cell = layout.cell(positions[0]);
+
+            let mut step = Step::new(Technique::HiddenSingle);
+            step.placements.push(Candidate::new(cell, value));
+            step.cells.push(cell);
+            step.values.push(value);
+            step.units.push(layout.units[unit_idx]);
+            return Some(step);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,104 @@
+use candidates::{Candidate, Candidates, mask_values};
+use logical_solver::{combinations, Layout, Step, Technique};
+
+fn naked_technique(size: usize) -> Technique {
+    match size {
+        2 => Technique::NakedPair,
+        3 => Technique::NakedTriple,
+        _ => Technique::NakedQuad,
+    }
+}
+
+fn hidden_technique(size: usize) -> Technique {
+    match size {
+        2 => Technique::HiddenPair,
+        3 => Technique::HiddenTriple,
+        _ => Technique::HiddenQuad,
+    }
+}
+
+//size cells of a unit whose candidates are limited to the same size values, so those values can
+//be removed from every other cell of the unit
+pub fn naked_subset(candidates: &Candidates, layout: &Layout, size: usize) -> Option<Step> {
+    for (unit_idx, cells) in layout.unit_cells.iter().enumerate() {
+        let open_cells: Vec<usize> = cells.iter().cloned()
+            .filter(|idx| candidates.value(*idx) == 0 && candidates.count(*idx) as usize <= size)
+            .collect();
+
+        for subset in combinations(&open_cells, size) {
+            let mask = subset.iter().fold(0, |mask, idx| mask | candidates.mask(*idx));
+            if mask.count_ones() as usize != size {
+                continue;
+            }
+
+            let mut step = Step::new(naked_technique(size));
+            let values = mask_values(mask);
+            for idx in cells.iter() {
+                if subset.contains(idx) {
+                    continue;
+                }
+                for value in values.iter() {
+                    if candidates.has(*idx, *value) {
+                        step.eliminations.push(Candidate::new(layout.cell(*idx), *value));
+                    }
+                }
+            }
+            if step.eliminations.is_empty() {
+                continue;
+            }
+
+            step.cells = subset.iter().map(|idx| layout.cell(*idx)).collect();
+            step.values = values;
+            step.units.push(layout.units[unit_idx]);
+            return Some(step);
+        }
+    }
+
+    None
+}
+
+//size values of a unit that only fit in the same size cells, so every other candidate can be
+//removed from those cells
+pub fn hidden_subset(candidates: &Candidates, layout: &Layout, size: usize) -> Option<Step> {
+    let side = layout.geometry.side();
+
+    for unit_idx in 0..layout.units.len() {
+        let open_values: Vec<u8> = (1..side as u8 + 1).filter(|value| {
+            let count = layout.positions(candidates, unit_idx, *value).len();
+            (1..=size).contains(&count)
+        }).collect();
+
+        for subset in combinations(&open_values, size) {
+            let mut positions: Vec<usize> = Vec::new();
+            for value in subset.iter() {
+                for idx in layout.positions(candidates, unit_idx, *value) {
+                    if !positions.contains(&idx) {
+                        positions.push(idx);
+                    }
+                }
+            }
+            if positions.len() != size {
+                continue;
+            }
+
+            let subset_mask = subset.iter().fold(0u32, |mask, value| mask | (1 << value));
+            let mut step = Step::new(hidden_technique(size));
+            positions.sort();
+            for idx in positions.iter() {
+                for value in mask_values(candidates.mask(*idx) & !subset_mask) {
+                    step.eliminations.push(Candidate::new(layout.cell(*idx), value));
+                }
+            }
+            if step.eliminations.is_empty() {
+                continue;
+            }
+
+            step.cells = positions.iter().map(|idx| layout.cell(*idx)).collect();
+            step.values = subset;
+            step.units.push(layout.units[unit_idx]);
+            return Some(step);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,277 @@
+use candidates::{mask_values, Candidate, Candidates};
+use logical_solver::{combinations, Layout, Step, Technique};
+
+//Four open cells on two rows, two columns and two blocks that could all hold the same two values.
+//If the four cells ended up with only those values they could be swapped, giving a second
+//solution, so a puzzle with a unique solution never allows it.
+struct Rectangle {
+    //Top left, top right, bottom left, bottom right
+    cells: [usize; 4],
+    values: [u8; 2],
+}
+
+impl Rectangle {
+    fn mask(&self) -> u32 {
+        (1 << self.values[0]) | (1 << self.values[1])
+    }
+
+    //The cells with candidates besides the two values
+    fn roof(&self, candidates: &Candidates) -> Vec<usize> {
+        self.cells.iter().cloned().filter(|idx| candidates.mask(*idx) != self.mask()).collect()
+    }
+}
+
+fn rectangle_technique(rectangle_type: usize) -> Technique {
+    match rectangle_type {
+        1 => Technique::UniqueRectangleType1,
+        2 => Technique::UniqueRectangleType2,
+        3 => Technique::UniqueRectangleType3,
+        4 => Technique::UniqueRectangleType4,
+        5 => Technique::UniqueRectangleType5,
+        _ => Technique::UniqueRectangleType6,
+    }
+}
+
+/// Finds a unique rectangle of the given type (1 to 6).
+pub fn unique_rectangle(candidates: &Candidates, layout: &Layout, rectangle_type: usize) -> Option<Step> {
+    for rectangle in rectangles(candidates, layout) {
+        let mut step = match rectangle_type {
+            1 => type_1(candidates, layout, &rectangle),
+            2 | 5 => type_2_or_5(candidates, layout, &rectangle, rectangle_type),
+            3 => type_3(candidates, layout, &rectangle),
+            4 => type_4(candidates, layout, &rectangle),
+            _ => type_6(candidates, layout, &rectangle),
+        };
+        if let Some(ref mut step) = step {
+            step.technique = rectangle_technique(rectangle_type);
+            let mut cells: Vec<usize> = rectangle.cells.to_vec();
+            cells.extend(step.cells.drain(..).map(|cell| layout.geometry.index_of(cell)));
+            step.cells = cells.iter().map(|idx| layout.cell(*idx)).collect();
+            step.values = rectangle.values.to_vec();
+        }
+        if step.is_some() {
+            return step;
+        }
+    }
+
+    None
+}
+
+//All of the values in a bivalue grid (every open cell has two candidates and every value two
+//places in a unit) could be swapped, so the only cell with a third candidate must take the value
+//that appears three times in its units
+pub fn bug_plus_one(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let open: Vec<usize> = (0..layout.geometry.cell_count()).filter(|idx| candidates.value(*idx) == 0).collect();
+    let trivalue: Vec<usize> = open.iter().cloned().filter(|idx| candidates.count(*idx) != 2).collect();
+    if trivalue.len() != 1 || candidates.count(trivalue[0]) != 3 {
+        return None;
+    }
+    let bug_cell = trivalue[0];
+
+    for value in mask_values(candidates.mask(bug_cell)) {
+        //Without this value every unit has to hold each of its open values exactly twice
+        let mut remaining = candidates.clone();
+        remaining.remove(bug_cell, value);
+        let is_bug = (0..layout.units.len()).all(|unit_idx| {
+            (1..layout.geometry.side() as u8 + 1).all(|other| {
+                layout.is_placed(&remaining, unit_idx, other) || layout.positions(&remaining, unit_idx, other).len() == 2
+            })
+        });
+        if !is_bug {
+            continue;
+        }
+
+        let mut step = Step::new(Technique::BugPlusOne);
+        step.placements.push(Candidate::new(layout.cell(bug_cell), value));
+        step.cells.push(layout.cell(bug_cell));
+        step.values.push(value);
+        return Some(step);
+    }
+
+    None
+}
+
+fn rectangles(candidates: &Candidates, layout: &Layout) -> Vec<Rectangle> {
+    let geometry = layout.geometry;
+    let side = geometry.side();
+    let mut rectangles = Vec::new();
+
+    for row1 in 0..side {
+        for row2 in row1 + 1..side {
+            for col1 in 0..side {
+                for col2 in col1 + 1..side {
+                    let cells = [row1 * side + col1, row1 * side + col2, row2 * side + col1, row2 * side + col2];
+                    if cells.iter().any(|idx| candidates.value(*idx) != 0) {
+                        continue;
+                    }
+                    let mut blocks: Vec<usize> = cells.iter().map(|idx| layout.cell_units[*idx][2]).collect();
+                    blocks.sort();
+                    blocks.dedup();
+                    if blocks.len() != 2 {
+                        continue;
+                    }
+
+                    let common = cells.iter().fold(u32::MAX, |mask, idx| mask & candidates.mask(*idx));
+                    for pair in combinations(&mask_values(common), 2) {
+                        rectangles.push(Rectangle { cells, values: [pair[0], pair[1]] });
+                    }
+                }
+            }
+        }
+    }
+
+    rectangles
+}
+
+//Whether two cells of the rectangle are on the same side rather than diagonally opposite
+fn share_side(layout: &Layout, idx1: usize, idx2: usize) -> bool {
+    let (units1, units2) = (layout.cell_units[idx1], layout.cell_units[idx2]);
+    units1[0] == units2[0] || units1[1] == units2[1]
+}
+
+//Three cells hold just the two values: the fourth needs one of its other candidates
+fn type_1(candidates: &Candidates, layout: &Layout, rectangle: &Rectangle) -> Option<Step> {
+    let roof = rectangle.roof(candidates);
+    if roof.len() != 1 {
+        return None;
+    }
+
+    let mut step = Step::new(Technique::UniqueRectangleType1);
+    for value in rectangle.values.iter() {
+        step.eliminations.push(Candidate::new(layout.cell(roof[0]), *value));
+    }
+    Some(step)
+}
+
+//The extra candidates all are the same single value: one of the cells holding it has to take it,
+//so it is removed from every cell that sees them all. Type 2 when the two cells share a side.
+fn type_2_or_5(candidates: &Candidates, layout: &Layout, rectangle: &Rectangle, rectangle_type: usize) -> Option<Step> {
+    let roof = rectangle.roof(candidates);
+    if roof.len() < 2 || roof.len() > 3 {
+        return None;
+    }
+    let extra = candidates.mask(roof[0]) & !rectangle.mask();
+    if extra.count_ones() != 1 || roof.iter().any(|idx| candidates.mask(*idx) & !rectangle.mask() != extra) {
+        return None;
+    }
+    let is_type_2 = roof.len() == 2 && share_side(layout, roof[0], roof[1]);
+    if is_type_2 != (rectangle_type == 2) {
+        return None;
+    }
+
+    let value = extra.trailing_zeros() as u8;
+    let mut step = Step::new(Technique::UniqueRectangleType2);
+    for idx in 0..layout.geometry.cell_count() {
+        if candidates.has(idx, value) && roof.iter().all(|roof_idx| layout.sees(idx, *roof_idx)) {
+            step.eliminations.push(Candidate::new(layout.cell(idx), value));
+        }
+    }
+    if step.eliminations.is_empty() {
+        return None;
+    }
+    Some(step)
+}
+
+//The extra candidates of two cells on one side act as a single cell that forms a naked subset
+//with other cells of a unit the two share
+fn type_3(candidates: &Candidates, layout: &Layout, rectangle: &Rectangle) -> Option<Step> {
+    let roof = rectangle.roof(candidates);
+    if roof.len() != 2 || !share_side(layout, roof[0], roof[1]) {
+        return None;
+    }
+    let extra = (candidates.mask(roof[0]) | candidates.mask(roof[1])) & !rectangle.mask();
+
+    for kind in 0..3 {
+        let unit_idx = layout.cell_units[roof[0]][kind];
+        if layout.cell_units[roof[1]][kind] != unit_idx {
+            continue;
+        }
+        let others: Vec<usize> = layout.unit_cells[unit_idx].iter().cloned()
+            .filter(|idx| candidates.value(*idx) == 0 && !roof.contains(idx))
+            .collect();
+
+        for size in 1..4 {
+            for subset in combinations(&others, size) {
+                let mask = subset.iter().fold(extra, |mask, idx| mask | candidates.mask(*idx));
+                if mask.count_ones() as usize != size + 1 {
+                    continue;
+                }
+
+                let mut step = Step::new(Technique::UniqueRectangleType3);
+                for idx in others.iter().filter(|idx| !subset.contains(idx)) {
+                    for value in mask_values(mask) {
+                        if candidates.has(*idx, value) {
+                            step.eliminations.push(Candidate::new(layout.cell(*idx), value));
+                        }
+                    }
+                }
+                if step.eliminations.is_empty() {
+                    continue;
+                }
+                step.cells = subset.iter().map(|idx| layout.cell(*idx)).collect();
+                step.units.push(layout.units[unit_idx]);
+                return Some(step);
+            }
+        }
+    }
+
+    None
+}
+
+//One of the values only fits in the two cells on one side within a unit they share, so the other
+//value cannot go in either of them
+fn type_4(candidates: &Candidates, layout: &Layout, rectangle: &Rectangle) -> Option<Step> {
+    let roof = rectangle.roof(candidates);
+    if roof.len() != 2 || !share_side(layout, roof[0], roof[1]) {
+        return None;
+    }
+
+    for kind in 0..3 {
+        let unit_idx = layout.cell_units[roof[0]][kind];
+        if layout.cell_units[roof[1]][kind] != unit_idx {
+            continue;
+        }
+        for (locked, other) in [(0, 1), (1, 0)].iter() {
+            if layout.positions(candidates, unit_idx, rectangle.values[*locked]).len() != 2 {
+                continue;
+            }
+            let mut step = Step::new(Technique::UniqueRectangleType4);
+            for idx in roof.iter() {
+                step.eliminations.push(Candidate::new(layout.cell(*idx), rectangle.values[*other]));
+            }
+            step.units.push(layout.units[unit_idx]);
+            return Some(step);
+        }
+    }
+
+    None
+}
+
+//Two diagonally opposite cells hold just the two values and one of the values only fits in the
+//rectangle along both rows and both columns: it would have to go in the two bivalue cells
+fn type_6(candidates: &Candidates, layout: &Layout, rectangle: &Rectangle) -> Option<Step> {
+    let roof = rectangle.roof(candidates);
+    if roof.len() != 2 || share_side(layout, roof[0], roof[1]) {
+        return None;
+    }
+
+    for value in rectangle.values.iter() {
+        let lines: Vec<usize> = rectangle.cells.iter()
+            .flat_map(|idx| layout.cell_units[*idx][..2].to_vec())
+            .collect();
+        if lines.iter().any(|unit_idx| layout.positions(candidates, *unit_idx, *value).len() != 2) {
+            continue;
+        }
+
+        let mut step = Step::new(Technique::UniqueRectangleType6);
+        for idx in roof.iter() {
+            step.eliminations.push(Candidate::new(layout.cell(*idx), *value));
+        }
+        step.units = lines.iter().map(|unit_idx| layout.units[*unit_idx]).collect();
+        step.units.sort();
+        step.units.dedup();
+        return Some(step);
+    }
+
+    None
+}
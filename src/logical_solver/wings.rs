@@ -0,0 +1,154 @@
+use candidates::{mask_values, Candidate, Candidates};
+use logical_solver::{Chain, Detail, Layout, LinkKind, Step, Technique};
+
+//A bivalue pivot XY seeing the pincers XZ and YZ: whichever value the pivot takes, one of the
+//pincers is Z so cells seeing both pincers cannot be Z
+pub fn xy_wing(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let bivalue = bivalue_cells(candidates, layout);
+
+    for pivot in bivalue.iter().cloned() {
+        let pivot_values = mask_values(candidates.mask(pivot));
+        let (x, y) = (pivot_values[0], pivot_values[1]);
+
+        for pincer1 in bivalue.iter().cloned().filter(|idx| layout.sees(pivot, *idx)) {
+            let mask1 = candidates.mask(pincer1);
+            //The first pincer shares X with the pivot and brings in Z
+            if mask1 & (1 << x) == 0 || mask1 & (1 << y) != 0 {
+                continue;
+            }
+            let z = (mask1 & !(1 << x)).trailing_zeros() as u8;
+
+            for pincer2 in bivalue.iter().cloned().filter(|idx| layout.sees(pivot, *idx)) {
+                if candidates.mask(pincer2) != (1 << y) | (1 << z) {
+                    continue;
+                }
+                let eliminations = common_eliminations(candidates, layout, &[pincer1, pincer2], z);
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                let mut chain = Chain::new(Candidate::new(layout.cell(pincer1), z));
+                chain.push(LinkKind::Strong, Candidate::new(layout.cell(pincer1), x));
+                chain.push(LinkKind::Weak, Candidate::new(layout.cell(pivot), x));
+                chain.push(LinkKind::Strong, Candidate::new(layout.cell(pivot), y));
+                chain.push(LinkKind::Weak, Candidate::new(layout.cell(pincer2), y));
+                chain.push(LinkKind::Strong, Candidate::new(layout.cell(pincer2), z));
+
+                let mut step = wing_step(Technique::XYWing, layout, &[pivot, pincer1, pincer2], &[x, y, z], eliminations);
+                step.detail = Detail::Chain(chain);
+                return Some(step);
+            }
+        }
+    }
+
+    None
+}
+
+//A pivot XYZ seeing the pincers XZ and YZ: one of the three cells is Z so cells seeing all of
+//them cannot be Z
+pub fn xyz_wing(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let bivalue = bivalue_cells(candidates, layout);
+
+    for pivot in 0..layout.geometry.cell_count() {
+        if candidates.value(pivot) != 0 || candidates.count(pivot) != 3 {
+            continue;
+        }
+        let pivot_mask = candidates.mask(pivot);
+        let pincers: Vec<usize> = bivalue.iter().cloned()
+            .filter(|idx| layout.sees(pivot, *idx) && candidates.mask(*idx) & !pivot_mask == 0)
+            .collect();
+
+        for (position, pincer1) in pincers.iter().enumerate() {
+            for pincer2 in pincers[position + 1..].iter() {
+                let (mask1, mask2) = (candidates.mask(*pincer1), candidates.mask(*pincer2));
+                //Together the pincers cover the pivot and share exactly Z
+                if mask1 | mask2 != pivot_mask || (mask1 & mask2).count_ones() != 1 {
+                    continue;
+                }
+                let z = (mask1 & mask2).trailing_zeros() as u8;
+                let eliminations = common_eliminations(candidates, layout, &[pivot, *pincer1, *pincer2], z);
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                return Some(wing_step(Technique::XYZWing, layout, &[pivot, *pincer1, *pincer2], &mask_values(pivot_mask), eliminations));
+            }
+        }
+    }
+
+    None
+}
+
+//Two bivalue cells XY that do not see each other, joined by a conjugate pair on X whose ends see
+//one cell each: one of the two cells is Y so cells seeing both cannot be Y
+pub fn w_wing(candidates: &Candidates, layout: &Layout) -> Option<Step> {
+    let bivalue = bivalue_cells(candidates, layout);
+
+    for (position, cell1) in bivalue.iter().enumerate() {
+        for cell2 in bivalue[position + 1..].iter() {
+            let mask = candidates.mask(*cell1);
+            if candidates.mask(*cell2) != mask || layout.sees(*cell1, *cell2) {
+                continue;
+            }
+
+            for x in mask_values(mask) {
+                let y = (mask & !(1 << x)).trailing_zeros() as u8;
+                let eliminations = common_eliminations(candidates, layout, &[*cell1, *cell2], y);
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                for unit_idx in 0..layout.units.len() {
+                    let positions = layout.positions(candidates, unit_idx, x);
+                    if positions.len() != 2 || positions.contains(cell1) || positions.contains(cell2) {
+                        continue;
+                    }
+                    let (end1, end2) = if layout.sees(*cell1, positions[0]) && layout.sees(*cell2, positions[1]) {
+                        (positions[0], positions[1])
+                    } else if layout.sees(*cell1, positions[1]) && layout.sees(*cell2, positions[0]) {
+                        (positions[1], positions[0])
+                    } else {
+                        continue;
+                    };
+
+                    let mut chain = Chain::new(Candidate::new(layout.cell(*cell1), y));
+                    chain.push(LinkKind::Strong, Candidate::new(layout.cell(*cell1), x));
+                    chain.push(LinkKind::Weak, Candidate::new(layout.cell(end1), x));
+                    chain.push(LinkKind::Strong, Candidate::new(layout.cell(end2), x));
+                    chain.push(LinkKind::Weak, Candidate::new(layout.cell(*cell2), x));
+                    chain.push(LinkKind::Strong, Candidate::new(layout.cell(*cell2), y));
+
+                    let mut step = wing_step(Technique::WWing, layout, &[*cell1, *cell2, end1, end2], &[x, y], eliminations);
+                    step.units.push(layout.units[unit_idx]);
+                    step.detail = Detail::Chain(chain);
+                    return Some(step);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn bivalue_cells(candidates: &Candidates, layout: &Layout) -> Vec<usize> {
+    (0..layout.geometry.cell_count())
+        .filter(|idx| candidates.value(*idx) == 0 && candidates.count(*idx) == 2)
+        .collect()
+}
+
+//The cells other than `cells` that see every one of them and still have value as a candidate
+fn common_eliminations(candidates: &Candidates, layout: &Layout, cells: &[usize], value: u8) -> Vec<Candidate> {
+    (0..layout.geometry.cell_count())
+        .filter(|idx| candidates.has(*idx, value) && cells.iter().all(|cell| layout.sees(*idx, *cell)))
+        .map(|idx| Candidate::new(layout.cell(idx), value))
+        .collect()
+}
+
+fn wing_step(technique: Technique, layout: &Layout, cells: &[usize], values: &[u8], eliminations: Vec<Candidate>) -> Step {
+    let mut step = Step::new(technique);
+    step.cells = cells.iter().map(|idx| layout.cell(*idx)).collect();
+    step.values = values.to_vec();
+    step.values.sort();
+    step.eliminations = eliminations;
+    step
+}
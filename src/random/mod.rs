@@ -0,0 +1,39 @@
+//! A small seeded pseudo random number generator, so that generated puzzles can be reproduced from
+//! their seed without pulling in a dependency.
+
+/// SplitMix64: fast, statistically sound enough for shuffling and fully determined by its seed.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random {
+            state: seed,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        return z ^ (z >> 31);
+    }
+
+    /// A number in `0..bound`, which must not be empty.
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Cannot pick from an empty range!");
+        //Multiply-shift keeps the bias negligible for the small bounds used here
+        (((self.next_u64() >> 32) * bound as u64) >> 32) as usize
+    }
+
+    /// Shuffles the slice in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            let other = self.below(idx + 1);
+            items.swap(idx, other);
+        }
+    }
+}
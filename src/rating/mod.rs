@@ -0,0 +1,234 @@
+//! Sudoku Explainer style difficulty ratings.
+//!
+//! ```
+//! use sudoku_solver::Board;
+//! use sudoku_solver::rating::{rate, Tier};
+//!
+//! let board = Board::new("200005709900070000070001050008200000067000520000008100040300080000060002609800003").unwrap();
+//! let rating = rate(&board).unwrap();
+//! assert_eq!(rating.tier, Tier::Hard);
+//! println!("{}", rating);
+//! ```
+use std::fmt;
+
+use board::{Board, Unit, Unsolvable};
+use candidates::Candidates;
+use logical_solver::{Detail, LogicalSolver, SolveStatus, Step, Technique, ALS_TECHNIQUES, BASIC_TECHNIQUES,
+                     CHAIN_TECHNIQUES, FISH_TECHNIQUES, FORCING_TECHNIQUES, UNIQUENESS_TECHNIQUES};
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, Ord, PartialOrd)]
+pub enum Tier {
+    //Hidden singles only
+    Easy,
+    //Naked singles and locked candidates
+    Medium,
+    //Subsets, basic fish, wings and unique rectangles
+    Hard,
+    //Large fish, chains and almost locked sets
+    Expert,
+    //Forcing nets or brute force
+    Diabolical,
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Rating {
+    //The rating of the hardest step, from 1.0 up to 11.0 for boards only brute force can finish
+    pub score: f32,
+    pub tier: Tier,
+    pub hardest: Technique,
+}
+
+/// Rates the board by solving it with the techniques in increasing order of difficulty: the
+/// rating is that of the hardest step needed. Uniqueness techniques are used when the board has
+/// a unique solution, and a board the techniques cannot finish is rated as needing brute force.
+pub fn rate(board: &Board) -> Result<Rating, Unsolvable> {
+    rate_with(board, &techniques_by_rating(), Tier::Diabolical).ok_or(Unsolvable)
+}
+
+/// Rates the board like `rate` as long as it is no harder than `max`, otherwise returns `None`
+/// without trying any technique beyond that tier. The solve stops at the first step rated above
+/// `max`.
+pub fn rate_up_to(board: &Board, max: Tier) -> Option<Rating> {
+    if max == Tier::Diabolical {
+        return rate(board).ok();
+    }
+
+    let techniques: Vec<Technique> = techniques_by_rating().into_iter()
+        .filter(|technique| base_rating(*technique) <= highest_rating(max))
+        .collect();
+    rate_with(board, &techniques, max)
+}
+
+//Solves the board with the techniques in order and rates its hardest step, None if they cannot
+//finish it or a step is harder than max. Brute force is only allowed for diabolical boards.
+fn rate_with(board: &Board, techniques: &[Technique], max: Tier) -> Option<Rating> {
+    let limit = highest_rating(max);
+    let solution = LogicalSolver::with_techniques(techniques)
+        .assume_uniqueness(true)
+        .brute_force_fallback(max == Tier::Diabolical)
+        .solve_candidates_while(Candidates::from_board(board), |step| step_rating(step) <= limit);
+    if solution.status != SolveStatus::Solved {
+        return None;
+    }
+
+    //A board solved by its givens alone is as easy as it gets
+    let mut hardest = (10, Technique::HiddenSingle);
+    for step in solution.steps.iter() {
+        let rating = step_rating(step);
+        if rating > hardest.0 {
+            hardest = (rating, step.technique);
+        }
+    }
+
+    Some(Rating {
+        score: hardest.0 as f32 / 10.0,
+        tier: tier(hardest.0),
+        hardest: hardest.1,
+    })
+}
+
+/// The rating of a technique in tenths, before any adjustment for the length of its pattern.
+pub fn base_rating(technique: Technique) -> u32 {
+    match technique {
+        Technique::HiddenSingle => 12,
+        Technique::NakedSingle => 23,
+        Technique::PointingCandidates => 26,
+        Technique::ClaimingCandidates => 28,
+        Technique::NakedPair => 30,
+        Technique::XWing => 32,
+        Technique::HiddenPair => 34,
+        Technique::FinnedXWing => 34,
+        Technique::SashimiXWing => 35,
+        Technique::NakedTriple => 36,
+        Technique::Swordfish => 38,
+        Technique::HiddenTriple => 40,
+        Technique::FinnedSwordfish => 40,
+        Technique::SashimiSwordfish => 41,
+        Technique::XYWing => 42,
+        Technique::XYZWing => 44,
+        Technique::WWing => 44,
+        Technique::UniqueRectangleType1 => 45,
+        Technique::UniqueRectangleType2 => 45,
+        Technique::UniqueRectangleType3 => 45,
+        Technique::UniqueRectangleType4 => 46,
+        Technique::UniqueRectangleType5 => 46,
+        Technique::UniqueRectangleType6 => 46,
+        Technique::NakedQuad => 50,
+        Technique::Jellyfish => 52,
+        Technique::HiddenQuad => 54,
+        Technique::FinnedJellyfish => 54,
+        Technique::SashimiJellyfish => 55,
+        Technique::BugPlusOne => 56,
+        Technique::SimpleColoring => 62,
+        Technique::XChain => 65,
+        Technique::AlternatingInferenceChain => 70,
+        Technique::AlsXz => 75,
+        Technique::AlsXyWing => 80,
+        Technique::CellForcingChain => 83,
+        Technique::DeathBlossom => 85,
+        Technique::UnitForcingChain => 85,
+        Technique::DigitForcingChain => 87,
+        Technique::CellForcingNet => 90,
+        Technique::UnitForcingNet => 92,
+        Technique::DigitForcingNet => 95,
+        Technique::BruteForce => 110,
+    }
+}
+
+/// The rating of a step in tenths: the base rating of its technique, plus up to a few tenths for
+/// long chains, large sets and deep forcing branches.
+pub fn step_rating(step: &Step) -> u32 {
+    let mut rating = base_rating(step.technique);
+    match step.technique {
+        //Hidden singles are easier to spot in a block than in a row or column
+        Technique::HiddenSingle => {
+            if let Some(Unit::Row(_)) | Some(Unit::Col(_)) = step.units.first() {
+                rating = 15;
+            }
+        },
+        //Wings have a fixed shape
+        Technique::XYWing | Technique::WWing | Technique::BruteForce => {},
+        _ => {
+            let length = match step.detail {
+                Detail::Chain(ref chain) => chain.nodes.len(),
+                Detail::Als { ref sets, .. } => sets.iter().map(|set| set.cells.len()).sum::<usize>() + 2,
+                Detail::Forcing(ref branches) => branches.iter().map(|branch| branch.implications.len()).sum(),
+                Detail::Fish { .. } | Detail::None => 0,
+            };
+            rating += length_bonus(length);
+        },
+    }
+
+    rating
+}
+
+/// Every technique, the cheapest first. This is the order `rate` tries them in.
+pub fn techniques_by_rating() -> Vec<Technique> {
+    let mut techniques: Vec<Technique> = BASIC_TECHNIQUES.iter()
+        .chain(FISH_TECHNIQUES.iter())
+        .chain(UNIQUENESS_TECHNIQUES.iter())
+        .chain(CHAIN_TECHNIQUES.iter())
+        .chain(ALS_TECHNIQUES.iter())
+        .chain(FORCING_TECHNIQUES.iter())
+        .cloned()
+        .collect();
+    techniques.sort_by_key(|technique| base_rating(*technique));
+    techniques
+}
+
+//A tenth for every step past a length of 4 on the scale 4, 6, 8, 12, 16, 24, 32...
+fn length_bonus(length: usize) -> u32 {
+    let mut bonus = 0;
+    let mut limit = 4;
+    while length > limit {
+        limit = if bonus % 2 == 0 { limit * 3 / 2 } else { limit * 4 / 3 };
+        bonus += 1;
+    }
+    bonus
+}
+
+/// The tier of a rating in tenths.
+pub fn tier(rating: u32) -> Tier {
+    match rating {
+        0..=15 => Tier::Easy,
+        16..=28 => Tier::Medium,
+        29..=46 => Tier::Hard,
+        47..=89 => Tier::Expert,
+        _ => Tier::Diabolical,
+    }
+}
+
+//The highest rating that still falls into the tier
+fn highest_rating(tier: Tier) -> u32 {
+    match tier {
+        Tier::Easy => 15,
+        Tier::Medium => 28,
+        Tier::Hard => 46,
+        Tier::Expert => 89,
+        Tier::Diabolical => 110,
+    }
+}
+
+impl Tier {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Tier::Easy => "easy",
+            Tier::Medium => "medium",
+            Tier::Hard => "hard",
+            Tier::Expert => "expert",
+            Tier::Diabolical => "diabolical",
+        }
+    }
+}
+
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1} ({}, {})", self.score, self.tier, self.hardest)
+    }
+}
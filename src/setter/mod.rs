@@ -0,0 +1,94 @@
+//! Helps setters turn a draft with several solutions into a proper puzzle.
+//!
+//! ```
+//! use sudoku_solver::Board;
+//! use sudoku_solver::setter::analyze;
+//! use sudoku_solver::sudoku_solver_dancinglinks::has_unique_solution;
+//!
+//! let draft = Board::new("....6..1...6.......1.2..43.....8.9..86......2....45..3...41.....7.......28....5..").unwrap();
+//! let analysis = analyze(&draft, 10).unwrap();
+//! assert!(analysis.solutions.len() > 1);
+//!
+//! //Adding the suggested givens makes the draft unique
+//! let mut puzzle = draft.clone();
+//! for given in analysis.suggested_givens.iter() {
+//!     puzzle.set(given.cell.col, given.cell.row, given.value);
+//! }
+//! assert!(has_unique_solution(&puzzle));
+//! ```
+use board::{Board, Cell, Unsolvable};
+use candidates::Candidate;
+use sudoku_solver_dancinglinks::{has_unique_solution, sample_solutions};
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Analysis {
+    //Up to the requested number of solutions, in the order the search finds them
+    pub solutions: Vec<Board>,
+    //The blank cells whose value is not the same in every sampled solution
+    pub differing_cells: Vec<Cell>,
+    //Values of the first solution that make the draft unique when added as givens
+    pub suggested_givens: Vec<Candidate>,
+}
+
+/// Samples up to `sample_size` solutions of the draft, the cells they disagree on and the givens
+/// that would leave only the first of them. The suggestion is minimal in that none of its givens
+/// can be left out, though a different choice of cells may need fewer. A draft that is already
+/// unique gets no suggestions, one without any solution is `Unsolvable`.
+pub fn analyze(board: &Board, sample_size: usize) -> Result<Analysis, Unsolvable> {
+    let solutions = sample_solutions(board, sample_size.max(1));
+    if solutions.is_empty() {
+        return Err(Unsolvable);
+    }
+
+    let differing_cells = differing_cells(&solutions).into_iter().map(|idx| board.cell_at(idx)).collect();
+    let suggested_givens = suggest_givens(board, &solutions[0], sample_size.max(2));
+
+    Ok(Analysis {
+        solutions,
+        differing_cells,
+        suggested_givens,
+    })
+}
+
+//The cells that do not hold the same value in every solution
+fn differing_cells(solutions: &[Board]) -> Vec<usize> {
+    let first = solutions[0].cells();
+    (0..first.len())
+        .filter(|idx| solutions.iter().any(|solution| solution.cells()[*idx] != first[*idx]))
+        .collect()
+}
+
+//Repeatedly adds the value of target at the cell that rules out the most sampled solutions until
+//the board is unique, then drops every added given the others make unnecessary
+fn suggest_givens(board: &Board, target: &Board, sample_size: usize) -> Vec<Candidate> {
+    let geometry = board.geometry();
+    let mut cells = board.cells().to_vec();
+    let mut added = Vec::new();
+
+    loop {
+        let solutions = sample_solutions(&Board::from_cells_with_geometry(cells.clone(), geometry), sample_size);
+        if solutions.len() < 2 {
+            break;
+        }
+
+        let best = (0..cells.len())
+            .filter(|idx| cells[*idx] == 0)
+            .min_by_key(|idx| solutions.iter().filter(|solution| solution.cells()[*idx] == target.cells()[*idx]).count())
+            .expect("A board with several solutions has blank cells!");
+        cells[best] = target.cells()[best];
+        added.push(best);
+    }
+
+    //Later givens can make earlier ones unnecessary
+    added.retain(|idx| {
+        cells[*idx] = 0;
+        if has_unique_solution(&Board::from_cells_with_geometry(cells.clone(), geometry)) {
+            return false;
+        }
+        cells[*idx] = target.cells()[*idx];
+        true
+    });
+
+    added.sort();
+    return added.into_iter().map(|idx| Candidate::new(board.cell_at(idx), target.cells()[idx])).collect();
+}
@@ -1,113 +1,147 @@
+use board::{Board, Geometry, ParseError, Unsolvable};
+
 pub struct Sudoku {
-    pub board: Vec<u8>
+    pub board: Board
 }
 
 impl Sudoku {
-    pub fn new(board_string: &str) -> Sudoku {
-        assert_eq!(board_string.len(), 81, "Board is not correct size!");
-        let mut board = Vec::with_capacity(81);
-
-        for (idx, character) in board_string.chars().enumerate() {
-            match character.to_digit(10) {
-                Some(n) => {
-                    board.push(n as u8);
-                },
-                None => {
-                    if character == '_' || character == '.' {
-                        board.push(0);
-                    } else {
-                        panic!("Invalid character at index: {}", idx);
-                    }
-                }
-            }
-        }
+    pub fn new(board_string: &str) -> Result<Sudoku, ParseError> {
+        Ok(Sudoku {
+            board: Board::new(board_string)?
+        })
+    }
 
-        Sudoku {
-            board
+    /// Fills in the blank cells by brute force, returning `Unsolvable` when the givens contradict
+    /// each other or once every combination has been tried without success.
+    pub fn solve(&self) -> Result<Board, Unsolvable> {
+        let mut solution = None;
+        backtrack(self.board.cells(), self.board.geometry(), |board| {
+            solution = Some(Board::from_cells_with_geometry(board.to_vec(), self.board.geometry()));
+            //The first solution is all we need
+            return false;
+        });
+
+        solution.ok_or(Unsolvable)
+    }
+
+    /// Counts the solutions of the board, stopping the search as soon as `limit` have been found.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut count = 0;
+        if limit > 0 {
+            backtrack(self.board.cells(), self.board.geometry(), |_| {
+                count += 1;
+                return count < limit;
+            });
         }
+
+        count
     }
 
-    pub fn solve(&self) -> Vec<u8> {
-        let mut board_copy: Vec<u8> = self.board.clone();
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
 
-        // Find the cells that need to be solved
-        let mut blank_positions: Vec<usize> = self.board.iter().enumerate()
-            .filter(|&(idx, val)| {
-                return *val == 0;
-            }).map(|(idx, val)| {
-                return  idx;
-            }).collect();
+}
 
-        let mut pointer_idx = 0;
+/// Counts the solutions of the board with the backtracker, stopping once `limit` have been found.
+pub fn count_solutions(board: &Board, limit: usize) -> usize {
+    Sudoku { board: board.clone() }.count_solutions(limit)
+}
 
-        while pointer_idx < blank_positions.len() {
-            let current_blank_pos: &usize = &blank_positions[pointer_idx];
-            let mut target_cell_val: u8 = *&board_copy[*current_blank_pos as usize];
-            target_cell_val += 1;
-            target_cell_val %= 10;
+pub fn has_unique_solution(board: &Board) -> bool {
+    count_solutions(board, 2) == 1
+}
 
-            *board_copy.get_mut(*current_blank_pos).unwrap() = target_cell_val;
+//Runs the brute force search over the blank cells and calls on_solution with every completed board
+//until it asks to stop by returning false
+fn backtrack<F: FnMut(&[u8]) -> bool>(board: &[u8], geometry: Geometry, mut on_solution: F) {
+    let mut board_copy: Vec<u8> = board.to_vec();
+    let side = geometry.side();
+
+    //`board` is public so the givens may not have gone through `Board::new`... Make sure they
+    //don't already break the rules as the search below only ever checks the blank cells
+    for (idx, val) in board_copy.iter().enumerate() {
+        if *val != 0 && !is_valid_placement(&board_copy, geometry, idx, *val) {
+            return;
+        }
+    }
 
-            //Backtrack after going through all possible values(1-9)
-            if target_cell_val == 0 {
-                pointer_idx -= 1;
-                continue;
+    // Find the cells that need to be solved
+    let blank_positions: Vec<usize> = board_copy.iter().enumerate()
+        .filter(|&(_, val)| {
+            return *val == 0;
+        }).map(|(idx, _)| {
+            return idx;
+        }).collect();
+
+    let mut pointer_idx = 0;
+
+    loop {
+        //Every blank has been filled in
+        if pointer_idx == blank_positions.len() {
+            if !on_solution(&board_copy) {
+                return;
             }
+            //Look for the next solution by moving on from the value of the last blank
+            pointer_idx = match pointer_idx.checked_sub(1) {
+                Some(previous_idx) => previous_idx,
+                None => return,
+            };
+        }
 
-            //Do validity check
-            //Check row and column
-            let target_x = *current_blank_pos % 9;
-            let target_y = (*current_blank_pos - (*current_blank_pos % 9)) / 9;
-
-
-            let mut passed_row_col_check = true;
-            for i in 0..9 {
-                //Sweep across the row and column
-                let current_row_cell = target_y * 9 + i;
-                let current_col_cell = target_x + (9 * i);
-
-                //Do row check
-                if current_row_cell != *current_blank_pos {
-                    if *&board_copy[current_row_cell as usize] == target_cell_val {
-                        passed_row_col_check = false;
-                        break;
-                    }
-                }
-
-                //Do column check
-                if current_col_cell != *current_blank_pos {
-                    if *&board_copy[current_col_cell as usize] == target_cell_val {
-                        passed_row_col_check = false;
-                        break;
-                    }
-                }
-            }
-            //Passed column and row check ... => Check the block now
-            if passed_row_col_check {
-                let mut passed_block_check = true;
-                let block_top_left_cell = *current_blank_pos - (target_x % 3) - 9 * (target_y % 3);
-                for y_offset in 0..3 {
-                    for x_offset in 0..3 {
-                        //Sweep through the block
-                        let current_block_cell = block_top_left_cell + x_offset + 9 * y_offset;
-                        if current_block_cell != *current_blank_pos {
-                            if *&board_copy[current_block_cell as usize] == target_cell_val {
-                                passed_block_check = false;
-                                break;
-                            }
-                        }
-                    }
-                }
-
-                //Increment the counter if all the block check also passed
-                if passed_block_check {
-                    pointer_idx += 1;
-                }
+        let current_blank_pos: usize = blank_positions[pointer_idx];
+        let mut target_cell_val: u8 = board_copy[current_blank_pos];
+        target_cell_val += 1;
+        target_cell_val %= side as u8 + 1;
+
+        board_copy[current_blank_pos] = target_cell_val;
+
+        //Backtrack after going through all possible values(1-side)
+        if target_cell_val == 0 {
+            //Backtracking past the first blank means the whole search space has been exhausted
+            pointer_idx = match pointer_idx.checked_sub(1) {
+                Some(previous_idx) => previous_idx,
+                None => return,
+            };
+            continue;
+        }
 
-            }
+        //Move on to the next blank if the value does not conflict with any other cell
+        if is_valid_placement(&board_copy, geometry, current_blank_pos, target_cell_val) {
+            pointer_idx += 1;
+        }
+    }
+}
+
+//Checks that no other cell in the same row, column or block as `position` holds `value`
+fn is_valid_placement(board: &[u8], geometry: Geometry, position: usize, value: u8) -> bool {
+    let side = geometry.side();
+    let target_x = position % side;
+    let target_y = position / side;
+
+    for i in 0..side {
+        //Sweep across the row and column
+        let current_row_cell = target_y * side + i;
+        let current_col_cell = target_x + (side * i);
+
+        if current_row_cell != position && board[current_row_cell] == value {
+            return false;
         }
+        if current_col_cell != position && board[current_col_cell] == value {
+            return false;
+        }
+    }
 
-        board_copy
+    //Sweep through the block
+    let block_top_left_cell = position - (target_x % geometry.box_width) - side * (target_y % geometry.box_height);
+    for y_offset in 0..geometry.box_height {
+        for x_offset in 0..geometry.box_width {
+            let current_block_cell = block_top_left_cell + x_offset + side * y_offset;
+            if current_block_cell != position && board[current_block_cell] == value {
+                return false;
+            }
+        }
     }
 
-}
\ No newline at end of file
+    return true;
+}
@@ -0,0 +1,60 @@
+extern crate sudoku_solver;
+
+use sudoku_solver::{Board, Unsolvable};
+use sudoku_solver::board::Geometry;
+use sudoku_solver::candidates::full_mask;
+use sudoku_solver::sudoku_solver_dancinglinks::{backbone, solutions, solve_sudoku};
+
+const SOLVABLE: &str = "200005709900070000070001050008200000067000520000008100040300080000060002609800003";
+const AMBIGUOUS: &str = "....6..1...6.......1.2..43.....8.9..86......2....45..3...41.....7.......28....5..";
+const DEEP_CONTRADICTION: &str = "230005709900070000070001050008200000067000520000008100040300080000060002609800003";
+
+#[test]
+fn backbone_agrees_with_enumeration() {
+    let board = Board::new(AMBIGUOUS).unwrap();
+    let mut possible = vec![0u32; board.cells().len()];
+    for solution in solutions(&board) {
+        for (idx, value) in solution.cells().iter().enumerate() {
+            possible[idx] |= 1 << value;
+        }
+    }
+
+    let backbone = backbone(&board).unwrap();
+    for (idx, value) in board.cells().iter().enumerate() {
+        if *value == 0 {
+            assert_eq!(backbone.candidates.mask(idx), possible[idx], "{}", board.cell_at(idx));
+        } else {
+            assert_eq!(backbone.candidates.value(idx), *value);
+        }
+    }
+    for forced in backbone.forced.iter() {
+        assert_eq!(possible[board.index_of(forced.cell)], 1 << forced.value);
+    }
+    assert!(!backbone.forced.is_empty());
+}
+
+#[test]
+fn unique_board_forces_every_open_cell() {
+    let board = Board::new(SOLVABLE).unwrap();
+    let solution = solve_sudoku(&board).unwrap();
+    let backbone = backbone(&board).unwrap();
+
+    assert_eq!(backbone.forced.len(), board.cells().iter().filter(|&&val| val == 0).count());
+    for forced in backbone.forced.iter() {
+        assert_eq!(solution.get(forced.cell.col, forced.cell.row), forced.value);
+    }
+}
+
+#[test]
+fn empty_board_allows_everything() {
+    //Far too many solutions to enumerate
+    let board = Board::empty(Geometry::new(3, 3));
+    let backbone = backbone(&board).unwrap();
+    assert!(backbone.forced.is_empty());
+    assert!((0..81).all(|idx| backbone.candidates.mask(idx) == full_mask(9)));
+}
+
+#[test]
+fn unsolvable_board_has_no_backbone() {
+    assert_eq!(backbone(&Board::new(DEEP_CONTRADICTION).unwrap()), Err(Unsolvable));
+}
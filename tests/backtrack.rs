@@ -0,0 +1,100 @@
+//Regression tests for boards that used to underflow `pointer_idx` in the backtracker. Run them with
+//`cargo test --release` as well, where the underflow turned into an endless loop instead of a panic.
+extern crate sudoku_solver;
+
+use sudoku_solver::{Board, ParseError, Unsolvable};
+use sudoku_solver::board::{Cell, Geometry, Unit};
+use sudoku_solver::sudoku_solver_backtrack::Sudoku;
+
+const SOLVED: &str = "214685739935472861876931254198256347467193528523748196742319685381564972659827413";
+
+fn assert_solves(board_string: &str) {
+    let solution = Sudoku::new(board_string).unwrap().solve().unwrap();
+
+    //The solution has to keep the givens and complete the board
+    for (given, val) in board_string.chars().zip(solution.cells().iter()) {
+        if let Some(n) = given.to_digit(10) {
+            if n != 0 {
+                assert_eq!(n as u8, *val);
+            }
+        }
+    }
+    assert!(Board::new(&solution.to_string()).is_ok());
+    assert!(solution.is_complete());
+}
+
+fn assert_unsolvable(cells: Vec<u8>) {
+    let sudoku = Sudoku {
+        board: Board::from_cells(cells)
+    };
+    assert_eq!(sudoku.solve(), Err(Unsolvable));
+}
+
+#[test]
+fn rejects_malformed_board_strings() {
+    assert_eq!(Sudoku::new("").err(), Some(ParseError::UnsupportedSize { found: 0 }));
+    assert_eq!(Sudoku::new(&SOLVED[1..]).err(), Some(ParseError::UnsupportedSize { found: 80 }));
+
+    let mut invalid_character = String::from(SOLVED);
+    invalid_character.replace_range(10..11, "x");
+    assert_eq!(Sudoku::new(&invalid_character).err(),
+               Some(ParseError::InvalidCharacter { character: 'x', cell: Cell::new(1, 1) }));
+}
+
+#[test]
+fn rejects_duplicate_givens() {
+    let duplicate_in_row = "11...............................................................................";
+    assert_eq!(Sudoku::new(duplicate_in_row).err(),
+               Some(ParseError::DuplicateGiven { value: 1, unit: Unit::Row(0), first: Cell::new(0, 0), second: Cell::new(0, 1) }));
+
+    let duplicate_in_block = "1.........1......................................................................";
+    assert_eq!(Sudoku::new(duplicate_in_block).err(),
+               Some(ParseError::DuplicateGiven { value: 1, unit: Unit::Block(0), first: Cell::new(0, 0), second: Cell::new(1, 1) }));
+}
+
+#[test]
+fn solves_boards_without_underflowing() {
+    assert_solves(SOLVED);
+    assert_solves(".................................................................................");
+    assert_solves("200005709900070000070001050008200000067000520000008100040300080000060002609800003");
+    //Only the last cell is blank
+    assert_solves("21468573993547286187693125419825634746719352852374819674231968538156497265982741.");
+}
+
+#[test]
+fn reports_contradictory_boards() {
+    //The first blank has no possible value
+    assert_unsolvable(Board::new("12345678.........9...............................................................").unwrap().cells().to_vec());
+    //Exhausting the search has to stop at the first blank
+    assert_unsolvable(Board::new("230005709900070000070001050008200000067000520000008100040300080000060002609800003").unwrap().cells().to_vec());
+    //The last two blanks both need the 9 missing from row 1
+    assert_unsolvable(Board::new("1234567....................................8...........................8.........").unwrap().cells().to_vec());
+}
+
+#[test]
+fn reports_conflicting_givens_that_bypassed_parsing() {
+    //A complete board with a repeated value has no blank to search
+    let mut full_board: Vec<u8> = Board::new(SOLVED).unwrap().cells().to_vec();
+    full_board.swap(0, 1);
+    full_board[1] = full_board[0];
+    assert_unsolvable(full_board);
+
+    //Conflicting givens next to blanks that could otherwise be filled
+    let mut partial_board = vec![0; 81];
+    partial_board[0] = 5;
+    partial_board[80] = 5;
+    partial_board[8] = 5;
+    assert_unsolvable(partial_board);
+}
+
+#[test]
+fn keeps_non_square_geometry() {
+    //Blocks 2 wide and 4 tall rather than the 4x2 an 8x8 board gets by default
+    let geometry = Geometry::new(2, 4);
+    let board_string = "12345678....1234...............................................2";
+    let board = Board::with_geometry(board_string, geometry).unwrap();
+
+    let solution = Sudoku { board: board.clone() }.solve().unwrap();
+    assert_eq!(solution.geometry(), geometry);
+    assert!(Board::with_geometry(&solution.to_string(), geometry).is_ok());
+}
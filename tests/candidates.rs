@@ -0,0 +1,103 @@
+extern crate sudoku_solver;
+
+use sudoku_solver::{Board, ParseError, Unsolvable};
+use sudoku_solver::board::{Cell, Geometry, Unit};
+use sudoku_solver::candidates::Candidates;
+use sudoku_solver::sudoku_solver_dancinglinks::{count_candidate_solutions, count_solutions, solve_candidates, solve_sudoku};
+
+const SOLVABLE: &str = "200005709900070000070001050008200000067000520000008100040300080000060002609800003";
+const AMBIGUOUS: &str = "....6..1...6.......1.2..43.....8.9..86......2....45..3...41.....7.......28....5..";
+
+//Solutions of AMBIGUOUS, and of AMBIGUOUS with r1c1 fixed to its value in the first solution
+const ALL: usize = 714;
+const NARROWED: usize = 127;
+
+const GRID: &str = "
+.-----------.-----------.
+| 1    234  | 234  234  |
+| 234  234  | 1    234  |
+:-----------+-----------:
+| 234  1    | 234  234  |
+| 234  234  | 234  1    |
+'-----------'-----------'";
+
+#[test]
+fn printed_grid_parses_back() {
+    //SOLVABLE has open cells down to a single candidate, which must not be read back as placed
+    for board_string in [SOLVABLE, AMBIGUOUS, "1...........3..."].iter() {
+        let candidates = Candidates::from_board(&Board::new(board_string).unwrap());
+        assert_eq!(Candidates::parse(&candidates.to_string()), Ok(candidates));
+    }
+
+    let candidates = Candidates::from_board(&Board::empty(Geometry::new(3, 2)));
+    assert_eq!(Candidates::parse_with_geometry(&candidates.to_string(), Geometry::new(3, 2)), Ok(candidates));
+
+    //A cell without candidates is a contradiction that can still be saved and loaded
+    let mut candidates = Candidates::from_board(&Board::new(SOLVABLE).unwrap());
+    candidates.set_mask(1, 0);
+    candidates.set_mask(2, 1 << 4);
+    let grid = candidates.to_string();
+    assert!(grid.contains(" * ") && grid.contains(" 4* "));
+    assert_eq!(Candidates::parse(&grid), Ok(candidates));
+}
+
+#[test]
+fn parse_pencil_marks() {
+    let candidates = Candidates::parse(GRID).unwrap();
+    assert_eq!(candidates.value(0), 1);
+    assert_eq!(candidates.mask(1), 0b11100);
+    assert_eq!(candidates.to_board(), Board::new("1.....1..1.....1").unwrap());
+
+    let open = Candidates::parse(&GRID.replacen("1 ", "1*", 1)).unwrap();
+    assert_eq!(open.value(0), 0);
+    assert_eq!(open.mask(0), 0b10);
+
+    assert_eq!(Candidates::parse("12 34"), Err(ParseError::UnsupportedSize { found: 2 }));
+    assert_eq!(Candidates::parse(&GRID.replacen("234", "2*4", 1)),
+               Err(ParseError::InvalidCharacter { character: '*', cell: Cell::new(0, 1) }));
+    assert_eq!(Candidates::parse(&GRID.replacen("234", "2x4", 1)),
+               Err(ParseError::InvalidCharacter { character: 'x', cell: Cell::new(0, 1) }));
+    assert_eq!(Candidates::parse(&GRID.replacen("234", "235", 1)),
+               Err(ParseError::InvalidCharacter { character: '5', cell: Cell::new(0, 1) }));
+    assert_eq!(Candidates::parse(&GRID.replacen("234", "1", 1)),
+               Err(ParseError::DuplicateGiven { value: 1, unit: Unit::Row(0), first: Cell::new(0, 0), second: Cell::new(0, 1) }));
+}
+
+#[test]
+fn exact_cover_only_uses_remaining_candidates() {
+    let board = Board::new(SOLVABLE).unwrap();
+    let solution = solve_sudoku(&board).unwrap();
+    let mut candidates = Candidates::from_board(&board);
+    assert_eq!(solve_candidates(&candidates), Ok(solution.clone()));
+
+    //Removing a wrong candidate changes nothing, removing the right one leaves no solution
+    let idx = 1;
+    let value = solution.cells()[idx];
+    let wrong = (1..10).find(|other| *other != value && candidates.has(idx, *other)).unwrap();
+    candidates.remove(idx, wrong);
+    assert_eq!(solve_candidates(&candidates), Ok(solution.clone()));
+    candidates.remove(idx, value);
+    assert_eq!(solve_candidates(&candidates), Err(Unsolvable));
+
+    candidates.add(idx, value);
+    assert_eq!(solve_candidates(&candidates), Ok(solution));
+}
+
+#[test]
+fn pencil_marks_narrow_down_the_solutions() {
+    let board = Board::new(AMBIGUOUS).unwrap();
+    let mut candidates = Candidates::from_board(&board);
+    let all = count_solutions(&board, 1000);
+    assert_eq!(all, ALL);
+    assert_eq!(count_candidate_solutions(&candidates, 1000), all);
+
+    let idx = board.cells().iter().position(|&val| val == 0).unwrap();
+    let first = solve_sudoku(&board).unwrap().cells()[idx];
+    candidates.set_mask(idx, 1 << first);
+    assert_eq!(count_candidate_solutions(&candidates, 1000), NARROWED);
+
+    //The same as placing the value as a given
+    let mut cells = board.cells().to_vec();
+    cells[idx] = first;
+    assert_eq!(count_solutions(&Board::from_cells(cells), 1000), NARROWED);
+}
@@ -0,0 +1,84 @@
+extern crate sudoku_solver;
+
+use sudoku_solver::board::Geometry;
+use sudoku_solver::generator::{Generator, Symmetry};
+use sudoku_solver::rating::{rate, Tier};
+use sudoku_solver::sudoku_solver_dancinglinks::{has_unique_solution, solve_sudoku};
+
+#[test]
+fn same_seed_same_puzzle() {
+    let first = Generator::new(42).generate();
+    let second = Generator::new(42).generate();
+    assert_eq!(first, second);
+
+    let other = Generator::new(43).generate();
+    assert_ne!(first.solution, other.solution);
+}
+
+#[test]
+fn puzzles_have_a_unique_solution() {
+    for seed in 0..5 {
+        let puzzle = Generator::new(seed).generate();
+        assert!(puzzle.solution.is_complete());
+        assert!(has_unique_solution(&puzzle.board));
+        assert_eq!(solve_sudoku(&puzzle.board), Ok(puzzle.solution.clone()));
+
+        //Every given is taken from the solution
+        for (given, value) in puzzle.board.cells().iter().zip(puzzle.solution.cells()) {
+            assert!(*given == 0 || given == value);
+        }
+    }
+}
+
+#[test]
+fn removal_stops_at_the_target() {
+    let puzzle = Generator::new(3).target_clues(40).generate();
+    assert_eq!(puzzle.board.clue_count(), 40);
+
+    //Without a target every remaining clue is needed
+    let puzzle = Generator::new(3).generate();
+    assert!(puzzle.board.clue_count() < 40);
+}
+
+#[test]
+fn other_geometries() {
+    let puzzle = Generator::new(9).geometry(Geometry::new(3, 2)).generate();
+    assert_eq!(puzzle.board.side(), 6);
+    assert!(has_unique_solution(&puzzle.board));
+}
+
+#[test]
+fn givens_follow_the_symmetry() {
+    for symmetry in Symmetry::ALL.iter() {
+        let puzzle = Generator::new(11).symmetry(*symmetry).generate();
+        assert!(has_unique_solution(&puzzle.board), "{:?}", symmetry);
+
+        let cells = puzzle.board.cells();
+        for idx in 0..cells.len() {
+            for image in symmetry.orbit(9, idx) {
+                assert_eq!(cells[idx] == 0, cells[image] == 0, "{:?} {} {}", symmetry, idx, image);
+            }
+        }
+    }
+}
+
+#[test]
+fn rated_puzzles_land_in_the_range() {
+    for tier in [Tier::Easy, Tier::Hard].iter() {
+        let generator = Generator::new(2).difficulty(*tier, *tier);
+        let rated = generator.generate_rated().unwrap();
+        assert_eq!(rated.rating.tier, *tier);
+        assert_eq!(rate(&rated.puzzle.board), Ok(rated.rating));
+        assert!(has_unique_solution(&rated.puzzle.board));
+
+        //The seed of the puzzle reproduces it
+        let again = Generator::new(rated.puzzle.seed).difficulty(*tier, *tier).generate();
+        assert_eq!(again, rated.puzzle);
+    }
+}
+
+#[test]
+fn budget_runs_out() {
+    let result = Generator::new(0).difficulty(Tier::Diabolical, Tier::Diabolical).max_attempts(2).generate_rated();
+    assert_eq!(result.unwrap_err().discarded, 2);
+}
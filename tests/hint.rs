@@ -0,0 +1,81 @@
+extern crate sudoku_solver;
+
+use sudoku_solver::Board;
+use sudoku_solver::board::Geometry;
+use sudoku_solver::candidates::Candidates;
+use sudoku_solver::hint::{next_hint, HintResult};
+use sudoku_solver::logical_solver::Technique;
+use sudoku_solver::sudoku_solver_dancinglinks::solve_sudoku;
+
+const PUZZLE: &str = "200005709900070000070001050008200000067000520000008100040300080000060002609800003";
+
+#[test]
+fn hints_start_with_the_simplest_step() {
+    let board = Board::new(PUZZLE).unwrap();
+    match next_hint(&board, &Candidates::from_board(&board)) {
+        HintResult::Hint(hint) => {
+            assert_eq!(hint.technique, Technique::HiddenSingle);
+            assert_eq!(hint.placements.len(), 1);
+            assert_eq!(hint.targets, vec![hint.placements[0].cell]);
+            assert!(hint.explanation.starts_with(&hint.placements[0].value.to_string()));
+        },
+        other => panic!("expected a hint, got {:?}", other),
+    }
+}
+
+#[test]
+fn solved_boards_need_no_hint() {
+    let solution = solve_sudoku(&Board::new(PUZZLE).unwrap()).unwrap();
+    assert_eq!(next_hint(&solution, &Candidates::from_board(&solution)), HintResult::Solved);
+}
+
+#[test]
+fn wrong_entries_are_reported() {
+    let board = Board::new(PUZZLE).unwrap();
+    let candidates = Candidates::from_board(&board);
+
+    //A second 2 in row 1
+    let mut duplicate = board.clone();
+    duplicate.set(1, 0, 2);
+    match next_hint(&duplicate, &candidates) {
+        HintResult::Contradiction(contradiction) => assert_eq!(contradiction.cells.len(), 2),
+        other => panic!("expected a contradiction, got {:?}", other),
+    }
+
+    //A 3 at r1c2 breaks nothing locally but leaves no solution
+    let mut unsolvable = board.clone();
+    unsolvable.set(1, 0, 3);
+    assert!(matches!(next_hint(&unsolvable, &candidates), HintResult::Contradiction(_)));
+
+    //Pencil marks without the value that belongs in r1c2
+    let solution = solve_sudoku(&board).unwrap();
+    let mut marks = candidates.clone();
+    marks.remove(1, solution.get(1, 0));
+    match next_hint(&board, &marks) {
+        HintResult::Contradiction(contradiction) => assert_eq!(contradiction.cells, vec![board.cell_at(1)]),
+        other => panic!("expected a contradiction, got {:?}", other),
+    }
+}
+
+#[test]
+fn board_and_pencil_marks_must_agree() {
+    let board = Board::new(PUZZLE).unwrap();
+    let solution = solve_sudoku(&board).unwrap();
+
+    //The pencil marks hold the right value at r1c2 but the board has another entry there
+    let mut marks = Candidates::from_board(&board);
+    marks.place(1, solution.get(1, 0));
+    let mut entered = board.clone();
+    entered.set(1, 0, 3);
+    match next_hint(&entered, &marks) {
+        HintResult::Contradiction(contradiction) => assert_eq!(contradiction.cells, vec![board.cell_at(1)]),
+        other => panic!("expected a contradiction, got {:?}", other),
+    }
+
+    //Pencil marks of a 4x4 board cannot go with a 9x9 board
+    let small = Candidates::from_board(&Board::empty(Geometry::new(2, 2)));
+    match next_hint(&board, &small) {
+        HintResult::Contradiction(contradiction) => assert!(contradiction.cells.is_empty()),
+        other => panic!("expected a contradiction, got {:?}", other),
+    }
+}
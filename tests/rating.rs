@@ -0,0 +1,85 @@
+extern crate sudoku_solver;
+
+use sudoku_solver::Board;
+use sudoku_solver::candidates::Candidates;
+use sudoku_solver::logical_solver::{LogicalSolver, SolveStatus, Technique};
+use sudoku_solver::rating::{rate, rate_up_to, step_rating, techniques_by_rating, tier, Rating, Tier};
+
+//Hidden singles in blocks are all it takes
+const SINGLES: &str = "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+const NAKED_SINGLES: &str = "000000000000003085001020000000507000004000100090000000500000073002010000000040009";
+const FINNED_FISH: &str = ".6.23..5..5.....6.......7.11...52...5..78..3.........9..3..........91.7..72.....4";
+const CHAIN: &str = "......84.96..3...5...1...37..1..2...63..9.....79..1.......5.2..25...7..9..3....7.";
+//None of the techniques make any progress on this one
+const BRUTE_FORCE: &str = "1.......2.9.4...5...6...7...5.9.3.......7.......85..4.7.....6...3...9.8...2.....1";
+
+fn rating(score: f32, tier: Tier, hardest: Technique) -> Rating {
+    Rating {
+        score,
+        tier,
+        hardest,
+    }
+}
+
+#[test]
+fn known_ratings() {
+    let expected = [
+        (SINGLES, rating(1.2, Tier::Easy, Technique::HiddenSingle)),
+        (NAKED_SINGLES, rating(2.3, Tier::Medium, Technique::NakedSingle)),
+        (FINNED_FISH, rating(3.4, Tier::Hard, Technique::FinnedXWing)),
+        (CHAIN, rating(7.3, Tier::Expert, Technique::AlternatingInferenceChain)),
+        (BRUTE_FORCE, rating(11.0, Tier::Diabolical, Technique::BruteForce)),
+    ];
+    for (board_string, rating) in expected.iter() {
+        assert_eq!(rate(&Board::new(board_string).unwrap()), Ok(*rating), "{}", board_string);
+    }
+
+    //A solved board is rated like a single
+    let solution = LogicalSolver::new().solve(&Board::new(SINGLES).unwrap()).board;
+    assert_eq!(rate(&solution), Ok(rating(1.0, Tier::Easy, Technique::HiddenSingle)));
+}
+
+#[test]
+fn tier_boundaries() {
+    let boundaries = [(15, Tier::Easy, Tier::Medium), (28, Tier::Medium, Tier::Hard), (46, Tier::Hard, Tier::Expert),
+                      (89, Tier::Expert, Tier::Diabolical)];
+    for (highest, below, above) in boundaries.iter() {
+        assert_eq!(tier(*highest), *below);
+        assert_eq!(tier(*highest + 1), *above);
+    }
+    assert_eq!(tier(0), Tier::Easy);
+    assert_eq!(tier(110), Tier::Diabolical);
+}
+
+#[test]
+fn rate_up_to_matches_rate_within_the_limit() {
+    for board_string in [SINGLES, NAKED_SINGLES, FINNED_FISH, CHAIN].iter() {
+        let board = Board::new(board_string).unwrap();
+        let rating = rate(&board).unwrap();
+        for max in [Tier::Easy, Tier::Medium, Tier::Hard, Tier::Expert, Tier::Diabolical].iter() {
+            let expected = if rating.tier <= *max { Some(rating) } else { None };
+            assert_eq!(rate_up_to(&board, *max), expected, "{} {}", board_string, max);
+        }
+    }
+    assert_eq!(rate_up_to(&Board::new(BRUTE_FORCE).unwrap(), Tier::Expert), None);
+}
+
+#[test]
+fn rate_up_to_stops_at_the_first_harder_step() {
+    //The full solve of the chain puzzle needs steps rated way past hard, so a solve limited to hard
+    //gives up right after the first of them
+    let board = Board::new(CHAIN).unwrap();
+    let solver = LogicalSolver::with_techniques(&techniques_by_rating()).assume_uniqueness(true);
+    let full = solver.solve(&board);
+    let limited = solver.solve_candidates_while(Candidates::from_board(&board), |step| tier(step_rating(step)) <= Tier::Hard);
+
+    assert_eq!(full.status, SolveStatus::Solved);
+    assert_eq!(limited.status, SolveStatus::Stuck);
+    assert!(limited.steps.len() < full.steps.len());
+    assert_eq!(limited.steps[..], full.steps[..limited.steps.len()]);
+
+    let (last, before) = limited.steps.split_last().unwrap();
+    assert!(tier(step_rating(last)) > Tier::Hard);
+    assert!(before.iter().all(|step| tier(step_rating(step)) <= Tier::Hard));
+    assert_eq!(rate_up_to(&board, Tier::Hard), None);
+}
@@ -0,0 +1,60 @@
+extern crate sudoku_solver;
+
+use sudoku_solver::{Board, Unsolvable};
+use sudoku_solver::board::Geometry;
+use sudoku_solver::setter::analyze;
+use sudoku_solver::sudoku_solver_dancinglinks::{has_unique_solution, solve_sudoku};
+
+const AMBIGUOUS: &str = "....6..1...6.......1.2..43.....8.9..86......2....45..3...41.....7.......28....5..";
+const UNIQUE: &str = "200005709900070000070001050008200000067000520000008100040300080000060002609800003";
+
+fn with_givens(board: &Board, givens: &[(usize, u8)]) -> Board {
+    let mut cells = board.cells().to_vec();
+    for &(idx, value) in givens.iter() {
+        cells[idx] = value;
+    }
+    Board::from_cells_with_geometry(cells, board.geometry())
+}
+
+#[test]
+fn suggested_givens_are_all_needed() {
+    for board in [Board::new(AMBIGUOUS).unwrap(), Board::empty(Geometry::new(2, 2))].iter() {
+        let analysis = analyze(board, 10).unwrap();
+        assert!(analysis.solutions.len() > 1);
+        assert!(!analysis.suggested_givens.is_empty());
+
+        let givens: Vec<(usize, u8)> = analysis.suggested_givens.iter()
+            .map(|given| (board.index_of(given.cell), given.value))
+            .collect();
+        let puzzle = with_givens(board, &givens);
+        assert!(has_unique_solution(&puzzle));
+        assert!(analysis.solutions.contains(&solve_sudoku(&puzzle).unwrap()));
+
+        for skipped in 0..givens.len() {
+            let mut fewer = givens.clone();
+            fewer.remove(skipped);
+            assert!(!has_unique_solution(&with_givens(board, &fewer)));
+        }
+    }
+}
+
+#[test]
+fn differing_cells_are_blank() {
+    let board = Board::new(AMBIGUOUS).unwrap();
+    let analysis = analyze(&board, 10).unwrap();
+    assert!(!analysis.differing_cells.is_empty());
+    for cell in analysis.differing_cells.iter() {
+        assert_eq!(board.get(cell.col, cell.row), 0);
+    }
+}
+
+#[test]
+fn unique_and_unsolvable_drafts() {
+    let analysis = analyze(&Board::new(UNIQUE).unwrap(), 10).unwrap();
+    assert_eq!(analysis.solutions.len(), 1);
+    assert!(analysis.differing_cells.is_empty());
+    assert!(analysis.suggested_givens.is_empty());
+
+    let board = Board::new("230005709900070000070001050008200000067000520000008100040300080000060002609800003").unwrap();
+    assert_eq!(analyze(&board, 10), Err(Unsolvable));
+}
@@ -0,0 +1,103 @@
+//Parses, solves and prints boards of every supported size with both solvers
+extern crate sudoku_solver;
+
+use sudoku_solver::{Board, ParseError};
+use sudoku_solver::board::Geometry;
+use sudoku_solver::sudoku_solver_backtrack::Sudoku;
+use sudoku_solver::sudoku_solver_dancinglinks::solve_sudoku;
+
+const SIDE_4: &str = "1..4..2..1..3..2";
+const SIDE_16: &str = "2A.GE.9.78.5DC.676.1532..94...8..EF98.DG.6CA735....5B7.C1...A2..12.6A9..5C384..D.7DC.83B...6..92GB9..54.FD271...58.....29.1EG..791.8.6E4.F..C57A.4..CD75AG.1F.....6D2.A..37.EG4BAF7EGB89.5.43D..632B7G5.418F9A..D9..4..3...C.7..EG17.2C8...B64..8C5.9.B.E7GD213.";
+const SIDE_25: &str = "8.O4P57.6..K1H.DAML92.CNE2.CHEM.8.JOPI..F5...7K6B....AM1D..P49..67J8.3L.5.G.N.6..EGKCFJ.7A.2PH.M843I.7.GBO.HF.E.2..CNIK69.DJ.FO.9.D.KH32...MI.C.74B..JPLM2.A8I.16H4OD5.B.JE.79K..JIAFB.96L.N...H.8KOC.1...H.3.O7GEJ..8CAL.NM.5P2F1D4.CPM...B.K..6..2......A.D85....F3..9.KCGJ1..EI7.C.3.JPL..N7MGI9.E.2..HK5LI9J7E24...FP.8..5OAC.3.66..K..358.AE....DLMIF1.P2M..NHCK......J...7.PB.8.O73EL.GJPD..OFN.MKA.H..24.H.K5JNCA..PI7.2B43D..G9.LC.BM2I6FL5.DJ4.1E9..K3O7H.GPDO21...H.A.B.7.5..I.MNN..F6H9.B7..31..G..O.....G..EL8N1..7B.3J4.FA..HK.93HA1DBFM52...LE8IJ9CG7NO...5.9K46E.IAGFP2O..D3J1.M4.7ONL....1.H2.P.K3G5E...JM6P.3..7.CN85OE.H..A.IF.";
+
+fn assert_round_trip(board_string: &str, side: usize) {
+    let board = Board::new(board_string).unwrap();
+    assert_eq!(board.geometry(), Geometry::for_side(side).unwrap());
+    assert_eq!(board.to_string(), board_string);
+
+    let solution = solve_sudoku(&board).unwrap();
+    assert_eq!(Sudoku::new(board_string).unwrap().solve(), Ok(solution.clone()));
+    assert!(solution.is_complete());
+    for (given, value) in board.cells().iter().zip(solution.cells().iter()) {
+        assert!(*given == 0 || given == value);
+    }
+
+    //The printed solution is read back as the same board, letters included
+    let printed = solution.to_string();
+    assert_eq!(printed.len(), side * side);
+    assert_eq!(printed.parse::<Board>(), Ok(solution));
+    for value in 10..=side as u32 {
+        assert!(printed.contains(::std::char::from_digit(value, 36).unwrap().to_ascii_uppercase()));
+    }
+}
+
+//Replaces the value of the first given with a character one past the largest value
+fn assert_rejects_out_of_range(board_string: &str, character: char) {
+    let idx = board_string.find(|given| given != '.').unwrap();
+    let mut invalid = String::from(board_string);
+    invalid.replace_range(idx..idx + 1, &character.to_string());
+
+    let cell = Board::new(board_string).unwrap().cell_at(idx);
+    let error = ParseError::InvalidCharacter { character, cell };
+    assert_eq!(Board::new(&invalid), Err(error.clone()));
+    assert_eq!(Sudoku::new(&invalid).err(), Some(error));
+
+    //Letters are read the same in lower case
+    assert_eq!(Board::new(&board_string.to_lowercase()), Board::new(board_string));
+}
+
+#[test]
+fn side_4() {
+    assert_round_trip(SIDE_4, 4);
+    assert_rejects_out_of_range(SIDE_4, '5');
+}
+
+#[test]
+fn side_16() {
+    assert_round_trip(SIDE_16, 16);
+    assert_rejects_out_of_range(SIDE_16, 'H');
+}
+
+#[test]
+fn side_25() {
+    assert_round_trip(SIDE_25, 25);
+    assert_rejects_out_of_range(SIDE_25, 'Q');
+}
+
+//A solved board of the geometry with every other cell blanked out, written for `Board::new`
+fn patterned_puzzle(geometry: Geometry) -> String {
+    let side = geometry.side();
+    (0..side * side)
+        .map(|idx| {
+            let (row, col) = (idx / side, idx % side);
+            let value = (geometry.box_width * (row % geometry.box_height) + row / geometry.box_height + col) % side + 1;
+            match (row + col) % 2 {
+                0 => ::std::char::from_digit(value as u32, 36).unwrap().to_ascii_uppercase(),
+                _ => '.',
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn rectangular_blocks() {
+    for &(side, box_width, box_height) in [(6, 3, 2), (10, 5, 2), (12, 4, 3)].iter() {
+        let geometry = Geometry::for_side(side).unwrap();
+        assert_eq!((geometry.box_width, geometry.box_height), (box_width, box_height));
+
+        let board_string = patterned_puzzle(geometry);
+        let board = Board::new(&board_string).unwrap();
+        assert_eq!(board.geometry(), geometry);
+        assert_eq!(board.to_string(), board_string);
+
+        let solutions = [solve_sudoku(&board).unwrap(), Sudoku::new(&board_string).unwrap().solve().unwrap()];
+        for solution in solutions.iter() {
+            assert_eq!(solution.geometry(), geometry);
+            assert!(solution.is_complete());
+            assert_eq!(Board::new(&solution.to_string()).as_ref(), Ok(solution));
+            for (given, value) in board.cells().iter().zip(solution.cells().iter()) {
+                assert!(*given == 0 || given == value);
+            }
+        }
+    }
+}
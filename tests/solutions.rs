@@ -0,0 +1,87 @@
+extern crate sudoku_solver;
+
+use sudoku_solver::Board;
+use sudoku_solver::exact_cover::Search;
+use sudoku_solver::random::Random;
+use sudoku_solver::sudoku_solver_dancinglinks::{build_exact_cover, count_solutions, solutions, solve_sudoku, Operation};
+
+const UNIQUE: &str = "200005709900070000070001050008200000067000520000008100040300080000060002609800003";
+//Only the first two rows are given, leaving a huge number of solutions
+const MANY: &str = "123456789456789123...............................................................";
+
+fn assert_solves(board: &Board, solution: &Board) {
+    assert!(solution.is_complete());
+    assert_eq!(solution.to_string().parse::<Board>().as_ref(), Ok(solution));
+    for (given, value) in board.cells().iter().zip(solution.cells().iter()) {
+        assert!(*given == 0 || given == value);
+    }
+}
+
+#[test]
+fn takes_several_solutions_of_an_ambiguous_board() {
+    let board = Board::new(MANY).unwrap();
+    let found: Vec<Board> = solutions(&board).take(2).collect();
+
+    assert_eq!(found.len(), 2);
+    assert_ne!(found[0], found[1]);
+    for solution in found.iter() {
+        assert_solves(&board, solution);
+    }
+}
+
+#[test]
+fn unique_board_has_a_single_solution() {
+    let board = Board::new(UNIQUE).unwrap();
+    let found: Vec<Board> = solutions(&board).collect();
+
+    assert_eq!(found, vec![solve_sudoku(&board).unwrap()]);
+    assert_solves(&board, &found[0]);
+}
+
+#[test]
+fn dropping_the_iterator_restores_the_network() {
+    let board = Board::new(MANY).unwrap();
+    let exact_cover = build_exact_cover(&board);
+    let first = exact_cover.solve().unwrap();
+
+    //Stop in the middle of the search, with most of the network still covered
+    {
+        let mut iterator = exact_cover.solutions();
+        assert_eq!(iterator.next(), Some(first.clone()));
+        assert!(iterator.next().is_some());
+    }
+
+    assert_eq!(exact_cover.solve(), Some(first.clone()));
+    assert_eq!(exact_cover.count_solutions(50), 50);
+    assert_eq!(exact_cover.solutions().take(3).count(), 3);
+    assert_eq!(count_solutions(&board, 50), 50);
+}
+
+#[test]
+fn shuffled_rows_find_the_same_solutions() {
+    //UNIQUE without the 2 at r1c1, which leaves it with four solutions
+    let board = Board::new(&format!(".{}", &UNIQUE[1..])).unwrap();
+    let exact_cover = build_exact_cover(&board);
+    let in_order: Vec<Vec<Operation>> = exact_cover.solutions().collect();
+    assert_eq!(in_order.len(), 4);
+
+    let mut firsts = Vec::new();
+    for seed in 0..10 {
+        let mut search = Search::new(exact_cover.column_header_root_id()).shuffle_rows(Random::new(seed));
+        let mut shuffled = Vec::new();
+        while let Some(solution) = search.next_solution(exact_cover.network()) {
+            assert!(in_order.iter().any(|other| same_rows(other, &solution)));
+            shuffled.push(solution);
+        }
+        assert_eq!(shuffled.len(), in_order.len());
+        firsts.push(shuffled.swap_remove(0));
+
+        //The exhausted search leaves the network as it found it
+        assert_eq!(exact_cover.solutions().count(), in_order.len());
+    }
+    assert!(firsts.iter().any(|first| !same_rows(first, &firsts[0])));
+}
+
+fn same_rows(solution: &[Operation], other: &[Operation]) -> bool {
+    solution.len() == other.len() && solution.iter().all(|row| other.contains(row))
+}
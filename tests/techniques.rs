@@ -0,0 +1,465 @@
+//Puzzles that need a particular technique: the techniques before it get stuck, and adding it makes
+//progress. Every step is checked against the dancing links solution, and the steps of the
+//technique under test against the pencil marks it was found in.
+extern crate sudoku_solver;
+
+use sudoku_solver::Board;
+use sudoku_solver::board::{Cell, Geometry, Unit};
+use sudoku_solver::candidates::{mask_values, Candidate, Candidates};
+use sudoku_solver::logical_solver::{Detail, ForcingLimits, LinkKind, LogicalSolver, SolveStatus, Step, Technique,
+                                    ALS_TECHNIQUES, BASIC_TECHNIQUES, FORCING_TECHNIQUES};
+use sudoku_solver::sudoku_solver_dancinglinks::solve_sudoku;
+
+//Solves the board with `before` alone, which has to get stuck, and again with `technique` added.
+//Returns every step of the technique along with the pencil marks it was found in.
+fn steps_of(board_string: &str, before: &[Technique], technique: Technique) -> Vec<(Candidates, Step)> {
+    let board = Board::new(board_string).unwrap();
+    let stuck = LogicalSolver::with_techniques(before).solve(&board);
+    assert_eq!(stuck.status, SolveStatus::Stuck, "{:?} is not needed", technique);
+
+    let mut techniques = before.to_vec();
+    techniques.push(technique);
+    let solution = LogicalSolver::with_techniques(&techniques).solve(&board);
+    assert_ne!(solution.status, SolveStatus::Contradiction);
+
+    let expected = solve_sudoku(&board).unwrap();
+    let mut candidates = Candidates::from_board(&board);
+    let mut found = Vec::new();
+    for step in solution.steps.iter() {
+        for placement in step.placements.iter() {
+            assert_eq!(expected.get(placement.cell.col, placement.cell.row), placement.value, "{}", step);
+        }
+        for elimination in step.eliminations.iter() {
+            assert_ne!(expected.get(elimination.cell.col, elimination.cell.row), elimination.value, "{}", step);
+        }
+        if step.technique == technique {
+            found.push((candidates.clone(), step.clone()));
+        }
+        step.apply(&mut candidates);
+    }
+
+    assert!(!found.is_empty(), "{:?} did not fire", technique);
+    found
+}
+
+//The techniques of BASIC_TECHNIQUES that come before technique
+fn basics_before(technique: Technique) -> Vec<Technique> {
+    BASIC_TECHNIQUES.iter().cloned().take_while(|other| *other != technique).collect()
+}
+
+fn cells_of(candidates: &Candidates, unit: Unit) -> Vec<usize> {
+    candidates.geometry().unit_cells(unit)
+}
+
+//The open cells of the unit that still hold the value
+fn positions(candidates: &Candidates, unit: Unit, value: u8) -> Vec<usize> {
+    cells_of(candidates, unit).into_iter().filter(|idx| candidates.has(*idx, value)).collect()
+}
+
+fn sees(candidates: &Candidates, cell1: Cell, cell2: Cell) -> bool {
+    let geometry = candidates.geometry();
+    cell1 != cell2 && geometry.cell_units(cell1).iter().any(|unit| geometry.cell_units(cell2).contains(unit))
+}
+
+fn indices(candidates: &Candidates, step: &Step) -> Vec<usize> {
+    step.cells.iter().map(|cell| candidates.geometry().index_of(*cell)).collect()
+}
+
+#[test]
+fn hidden_single() {
+    let board = "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......";
+    for (candidates, step) in steps_of(board, &[], Technique::HiddenSingle) {
+        let value = step.placements[0].value;
+        assert_eq!(step.cells, vec![step.placements[0].cell]);
+        assert_eq!(positions(&candidates, step.units[0], value), indices(&candidates, &step));
+    }
+}
+
+#[test]
+fn naked_single() {
+    let board = "..6.........3...1...9.4.3..4....9.3...18.4.2....5....9.7..9...521........68.7....";
+    for (candidates, step) in steps_of(board, &basics_before(Technique::NakedSingle), Technique::NakedSingle) {
+        let placement = step.placements[0];
+        assert_eq!(step.cells, vec![placement.cell]);
+        assert_eq!(candidates.mask(candidates.geometry().index_of(placement.cell)), 1 << placement.value);
+    }
+}
+
+//The value of a locked candidates step is confined to the cells in the first unit, and removed
+//from the second one
+fn assert_locked(candidates: &Candidates, step: &Step) {
+    let value = step.values[0];
+    let cells = indices(candidates, step);
+    assert_eq!(positions(candidates, step.units[0], value), cells);
+    for idx in cells.iter() {
+        assert!(cells_of(candidates, step.units[1]).contains(idx));
+    }
+    for elimination in step.eliminations.iter() {
+        let idx = candidates.geometry().index_of(elimination.cell);
+        assert_eq!(elimination.value, value);
+        assert!(cells_of(candidates, step.units[1]).contains(&idx) && !cells.contains(&idx));
+    }
+}
+
+#[test]
+fn pointing_candidates() {
+    let board = "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......";
+    for (candidates, step) in steps_of(board, &basics_before(Technique::PointingCandidates), Technique::PointingCandidates) {
+        match step.units[0] {
+            Unit::Block(_) => {},
+            unit => panic!("pointing from {}", unit),
+        }
+        assert_locked(&candidates, &step);
+    }
+}
+
+#[test]
+fn claiming_candidates() {
+    let board = "..6.........3...1...9.4.3..4....9.3...18.4.2....5....9.7..9...521........68.7....";
+    for (candidates, step) in steps_of(board, &basics_before(Technique::ClaimingCandidates), Technique::ClaimingCandidates) {
+        match step.units[1] {
+            Unit::Block(_) => {},
+            unit => panic!("claiming into {}", unit),
+        }
+        assert_locked(&candidates, &step);
+    }
+}
+
+//size cells of the unit hold nothing but the values of the step, which are removed from the rest
+//of the unit
+fn assert_naked_subset(board: &str, technique: Technique, size: usize) {
+    for (candidates, step) in steps_of(board, &basics_before(technique), technique) {
+        let cells = indices(&candidates, &step);
+        assert_eq!((cells.len(), step.values.len()), (size, size));
+
+        let mask = cells.iter().fold(0, |mask, idx| mask | candidates.mask(*idx));
+        assert_eq!(mask_values(mask), step.values);
+        for idx in cells.iter() {
+            assert!(cells_of(&candidates, step.units[0]).contains(idx));
+        }
+        for elimination in step.eliminations.iter() {
+            let idx = candidates.geometry().index_of(elimination.cell);
+            assert!(step.values.contains(&elimination.value));
+            assert!(cells_of(&candidates, step.units[0]).contains(&idx) && !cells.contains(&idx));
+        }
+    }
+}
+
+//size values of the unit only fit in the cells of the step, whose other candidates are removed
+fn assert_hidden_subset(board: &str, technique: Technique, size: usize) {
+    for (candidates, step) in steps_of(board, &basics_before(technique), technique) {
+        let cells = indices(&candidates, &step);
+        assert_eq!((cells.len(), step.values.len()), (size, size));
+
+        for value in step.values.iter() {
+            assert!(positions(&candidates, step.units[0], *value).iter().all(|idx| cells.contains(idx)));
+        }
+        for elimination in step.eliminations.iter() {
+            assert!(step.cells.contains(&elimination.cell));
+            assert!(!step.values.contains(&elimination.value));
+        }
+    }
+}
+
+#[test]
+fn naked_subsets() {
+    assert_naked_subset("..4..93..7...65....1.8..2.....458..........74.2.93.........3..8.7......1.9.....6.", Technique::NakedPair, 2);
+    assert_naked_subset("....14....3....2...7..........9...3.6.1.............8.2.....1.4....5.6.....7.8...", Technique::NakedTriple, 3);
+    assert_naked_subset("....57..98.....6....2..1....584.6.3..3.........1........584.3...4.1739.8......2.4", Technique::NakedQuad, 4);
+}
+
+#[test]
+fn hidden_subsets() {
+    assert_hidden_subset("48.3............71.2.......7.5....6....2..8.............1.76...3.....4......5....", Technique::HiddenPair, 2);
+    assert_hidden_subset(".9.8..1........8..1..37..5.43....67..61..4...........1...7....2..89.6.....6.....3", Technique::HiddenTriple, 3);
+    assert_hidden_subset(".....9..7.....7.5.....268.412.6........392.....6.4..3.25.....1.7.....5...19..5.7.", Technique::HiddenQuad, 4);
+}
+
+//The value is confined to the cover lines within the base lines, apart from the fins, and removed
+//from the rest of the cover lines where it sees every fin
+fn assert_fish(board: &str, technique: Technique, size: usize, finned: bool) {
+    for (candidates, step) in steps_of(board, &BASIC_TECHNIQUES, technique) {
+        let (base_units, cover_units, fins) = match step.detail {
+            Detail::Fish { ref base_units, ref cover_units, ref fins } => (base_units, cover_units, fins),
+            ref detail => panic!("{:?}", detail),
+        };
+        assert_eq!((base_units.len(), cover_units.len()), (size, size));
+        assert_eq!(fins.is_empty(), !finned);
+
+        let value = step.values[0];
+        let geometry = candidates.geometry();
+        let in_units = |units: &Vec<Unit>, idx: usize| units.iter().any(|unit| cells_of(&candidates, *unit).contains(&idx));
+        for unit in base_units.iter() {
+            for idx in positions(&candidates, *unit, value) {
+                assert!(in_units(cover_units, idx) || fins.contains(&geometry.cell_at(idx)), "{}", step);
+            }
+        }
+        for elimination in step.eliminations.iter() {
+            let idx = geometry.index_of(elimination.cell);
+            assert_eq!(elimination.value, value);
+            assert!(in_units(cover_units, idx) && !in_units(base_units, idx), "{}", step);
+            assert!(fins.iter().all(|fin| sees(&candidates, *fin, elimination.cell)), "{}", step);
+        }
+    }
+}
+
+#[test]
+fn basic_fish() {
+    assert_fish("..37.6....56.....9.........5..2...9.4..5.3.17..2...5..7......5.2.1...48....4.17..", Technique::XWing, 2, false);
+    assert_fish(".9.8..1........8..1..37..5.43....67..61..4...........1...7....2..89.6.....6.....3", Technique::Swordfish, 3, false);
+    assert_fish(".9.8..1........8..1..37..5.43....67..61..4...........1...7....2..89.6.....6.....3", Technique::Jellyfish, 4, false);
+}
+
+#[test]
+fn finned_fish() {
+    assert_fish("....14....3....2...7..........9...3.6.1.............8.2.....1.4....5.6.....7.8...", Technique::FinnedXWing, 2, true);
+    assert_fish("....14....3....2...7..........9...3.6.1.............8.2.....1.4....5.6.....7.8...", Technique::FinnedSwordfish, 3, true);
+    assert_fish(".7..9...1....36.472........1.7...5..........2.3.27.1.6.85..3.....39......6.18....", Technique::FinnedJellyfish, 4, true);
+}
+
+#[test]
+fn sashimi_fish() {
+    assert_fish("....14....3....2...7..........9...3.6.1.............8.2.....1.4....5.6.....7.8...", Technique::SashimiXWing, 2, true);
+    assert_fish(".6.23..5..5.....6.......7.11...52...5..78..3.........9..3..........91.7..72.....4", Technique::SashimiSwordfish, 3, true);
+    assert_fish(".7..9...1....36.472........1.7...5..........2.3.27.1.6.85..3.....39......6.18....", Technique::SashimiJellyfish, 4, true);
+}
+
+//At most one of the candidates is true
+fn is_weak_link(candidates: &Candidates, node1: Candidate, node2: Candidate) -> bool {
+    (node1.cell == node2.cell && node1.value != node2.value) ||
+        (node1.value == node2.value && sees(candidates, node1.cell, node2.cell))
+}
+
+//At least one of the candidates is true: the only two values of a cell, or the only two positions
+//of a value in a unit
+fn is_strong_link(candidates: &Candidates, node1: Candidate, node2: Candidate) -> bool {
+    let geometry = candidates.geometry();
+    let (idx1, idx2) = (geometry.index_of(node1.cell), geometry.index_of(node2.cell));
+    if idx1 == idx2 {
+        return node1.value != node2.value && candidates.mask(idx1) == (1 << node1.value) | (1 << node2.value);
+    }
+    node1.value == node2.value && geometry.cell_units(node1.cell).iter().any(|unit| {
+        let mut both = vec![idx1, idx2];
+        both.sort();
+        positions(candidates, *unit, node1.value) == both
+    })
+}
+
+//Every link of the chain holds in the pencil marks, the chain starts and ends with a strong link so
+//one of its ends is true, and every elimination is weakly linked to both ends
+fn assert_chain(board: &str, technique: Technique) {
+    for (candidates, step) in steps_of(board, &BASIC_TECHNIQUES, technique) {
+        let chain = match step.detail {
+            Detail::Chain(ref chain) => chain,
+            ref detail => panic!("{:?}", detail),
+        };
+        assert_eq!(chain.nodes.len(), chain.links.len() + 1);
+        for (position, link) in chain.links.iter().enumerate() {
+            let (node1, node2) = (chain.nodes[position], chain.nodes[position + 1]);
+            match *link {
+                LinkKind::Strong => assert!(is_strong_link(&candidates, node1, node2), "{}", step),
+                LinkKind::Weak => assert!(is_weak_link(&candidates, node1, node2), "{}", step),
+            }
+        }
+
+        //Colorings can also end in a contradiction, so only the links are checked for them
+        if technique == Technique::SimpleColoring {
+            continue;
+        }
+        assert_eq!((chain.links.first(), chain.links.last()), (Some(&LinkKind::Strong), Some(&LinkKind::Strong)), "{}", step);
+        let ends = (chain.nodes[0], chain.nodes[chain.nodes.len() - 1]);
+        for elimination in step.eliminations.iter() {
+            assert!(is_weak_link(&candidates, *elimination, ends.0) && is_weak_link(&candidates, *elimination, ends.1), "{}", step);
+        }
+    }
+}
+
+#[test]
+fn wings() {
+    assert_chain("48.3............71.2.......7.5....6....2..8.............1.76...3.....4......5....", Technique::XYWing);
+    assert_chain("....14....3....2...7..........9...3.6.1.............8.2.....1.4....5.6.....7.8...", Technique::WWing);
+
+    //An XYZ-Wing is a pivot holding three values and two pincers holding two of them each
+    let board = "....14....3....2...7..........9...3.6.1.............8.2.....1.4....5.6.....7.8...";
+    for (candidates, step) in steps_of(board, &BASIC_TECHNIQUES, Technique::XYZWing) {
+        let cells = indices(&candidates, &step);
+        let masks: Vec<u32> = cells.iter().map(|idx| candidates.mask(*idx)).collect();
+        assert_eq!(masks.iter().map(|mask| mask.count_ones()).collect::<Vec<u32>>(), vec![3, 2, 2]);
+        assert!(masks[1] & !masks[0] == 0 && masks[2] & !masks[0] == 0);
+        for elimination in step.eliminations.iter() {
+            assert!(step.cells.iter().all(|cell| sees(&candidates, *cell, elimination.cell)), "{}", step);
+        }
+    }
+}
+
+#[test]
+fn chains() {
+    let board = "....14....3....2...7..........9...3.6.1.............8.2.....1.4....5.6.....7.8...";
+    assert_chain(board, Technique::SimpleColoring);
+    assert_chain(board, Technique::XChain);
+    assert_chain("48.3............71.2.......7.5....6....2..8.............1.76...3.....4......5....", Technique::AlternatingInferenceChain);
+}
+
+//Every set is N cells of its unit holding N + 1 values between them
+fn assert_als(board: &str, technique: Technique, set_count: usize) {
+    for (candidates, step) in steps_of(board, &BASIC_TECHNIQUES, technique) {
+        let (sets, stem) = match step.detail {
+            Detail::Als { ref sets, ref stem, .. } => (sets, stem),
+            ref detail => panic!("{:?}", detail),
+        };
+        assert_eq!(stem.is_some(), technique == Technique::DeathBlossom);
+        if technique == Technique::DeathBlossom {
+            let stem = candidates.geometry().index_of(stem.unwrap());
+            assert_eq!(sets.len(), candidates.count(stem) as usize, "{}", step);
+        } else {
+            assert_eq!(sets.len(), set_count, "{}", step);
+        }
+
+        for set in sets.iter() {
+            assert_eq!(set.cells.len() + 1, set.values.len(), "{}", set);
+            let cells = cells_of(&candidates, set.unit);
+            let mask = set.cells.iter().fold(0, |mask, cell| {
+                let idx = candidates.geometry().index_of(*cell);
+                assert!(cells.contains(&idx) && candidates.value(idx) == 0, "{}", set);
+                mask | candidates.mask(idx)
+            });
+            assert_eq!(mask_values(mask), set.values, "{}", set);
+        }
+    }
+}
+
+#[test]
+fn almost_locked_sets() {
+    let board = "48.3............71.2.......7.5....6....2..8.............1.76...3.....4......5....";
+    assert_als(board, Technique::AlsXz, 2);
+    assert_als(board, Technique::AlsXyWing, 3);
+    assert_als(board, Technique::DeathBlossom, 0);
+}
+
+#[test]
+fn almost_locked_sets_on_large_boards() {
+    //Half of the cells of a patterned 25x25 grid, leaving hundreds of cells with few candidates
+    //between them to build sets from
+    let cells: Vec<u8> = (0..625)
+        .map(|idx| (idx / 25, idx % 25))
+        .map(|(row, col)| ((5 * (row % 5) + row / 5 + col) % 25 + 1) as u8)
+        .collect();
+    let solution = Board::from_cells(cells);
+    assert!(Board::with_geometry(&solution.to_string(), Geometry::new(5, 5)).is_ok());
+    let cells: Vec<u8> = solution.cells().iter().enumerate()
+        .map(|(idx, value)| if idx % 2 == 0 { *value } else { 0 })
+        .collect();
+    let candidates = Candidates::from_board(&Board::from_cells(cells));
+
+    for technique in ALS_TECHNIQUES.iter() {
+        if let Some(step) = LogicalSolver::with_techniques(&[*technique]).next_step(&candidates) {
+            for elimination in step.eliminations.iter() {
+                assert_ne!(solution.get(elimination.cell.col, elimination.cell.row), elimination.value, "{}", step);
+            }
+        }
+    }
+}
+
+//Either a single branch ends in a contradiction and its assumption is reversed, or every branch
+//reaches the conclusion of the step. Every implication follows from earlier ones, and from a
+//single one in a chain.
+fn assert_forcing(board: &str, technique: Technique, nets: bool) {
+    for (candidates, step) in steps_of(board, &BASIC_TECHNIQUES, technique) {
+        let branches = match step.detail {
+            Detail::Forcing(ref branches) => branches,
+            ref detail => panic!("{:?}", detail),
+        };
+        let conclusion = match (step.placements.first(), step.eliminations.first()) {
+            (Some(placement), None) => (*placement, true),
+            (None, Some(elimination)) => (*elimination, false),
+            _ => panic!("{}", step),
+        };
+
+        for branch in branches.iter() {
+            for (position, implication) in branch.implications.iter().enumerate() {
+                assert!(implication.reasons.iter().all(|reason| *reason < position), "{}", step);
+                assert!(position == 0 || !implication.reasons.is_empty(), "{}", step);
+                if !nets {
+                    assert!(implication.reasons.len() <= 1, "{}", step);
+                }
+            }
+        }
+
+        let assumption = &branches[0].implications[0];
+        if branches[0].contradiction.is_some() {
+            assert_eq!(branches.len(), 1);
+            assert_eq!((assumption.candidate, !assumption.is_true), conclusion, "{}", step);
+        } else {
+            assert!(branches.len() > 1 || technique == Technique::DigitForcingChain || technique == Technique::DigitForcingNet);
+            for branch in branches.iter() {
+                let last = branch.implications.last().unwrap();
+                assert_eq!((last.candidate, last.is_true), conclusion, "{}", step);
+            }
+        }
+
+        //The branches of a cell forcing chain assume each candidate of one cell in turn
+        if technique == Technique::CellForcingChain || technique == Technique::CellForcingNet {
+            let cell = assumption.candidate.cell;
+            assert!(branches.iter().all(|branch| branch.implications[0].candidate.cell == cell));
+            if branches[0].contradiction.is_none() {
+                assert_eq!(branches.len() as u32, candidates.count(candidates.geometry().index_of(cell)));
+            }
+        }
+    }
+}
+
+#[test]
+fn forcing_chains_and_nets() {
+    let board = "48.3............71.2.......7.5....6....2..8.............1.76...3.....4......5....";
+    assert_forcing(board, Technique::CellForcingChain, false);
+    assert_forcing(board, Technique::UnitForcingChain, false);
+    assert_forcing(board, Technique::DigitForcingChain, false);
+    assert_forcing(board, Technique::CellForcingNet, true);
+    assert_forcing(board, Technique::UnitForcingNet, true);
+    assert_forcing(board, Technique::DigitForcingNet, true);
+}
+
+#[test]
+fn forcing_limits() {
+    //Without following any implication no branch reaches a contradiction or a common implication,
+    //so the solver has to report that it is stuck
+    let board = Board::new("48.3............71.2.......7.5....6....2..8.............1.76...3.....4......5....").unwrap();
+    let mut techniques = BASIC_TECHNIQUES.to_vec();
+    techniques.extend(FORCING_TECHNIQUES.iter().cloned());
+    let limits = ForcingLimits { max_depth: 0, max_branches: 4 };
+    let solution = LogicalSolver::with_techniques(&techniques).forcing_limits(limits).solve(&board);
+    assert_eq!(solution.status, SolveStatus::Stuck);
+    assert!(solution.steps.iter().all(|step| !FORCING_TECHNIQUES.contains(&step.technique)));
+
+    assert_eq!(ForcingLimits::default(), ForcingLimits { max_depth: 12, max_branches: 4 });
+    assert_eq!(LogicalSolver::with_techniques(&techniques).solve(&board).status, SolveStatus::Solved);
+}
+
+#[test]
+fn standard_hard_puzzles() {
+    let expected = [
+        //From Peter Norvig's list of the hardest puzzles he found
+        ("12..4......5.69.1...9...5.........7.7...52.9..3......2.9.6...5.4..9..8.1..3...9.4", SolveStatus::Solved),
+        ("7.....4...2..7..8...3..8.799..5..3...6..2..9...1.97..6...3..9...3..4..6...9..1.35", SolveStatus::Solved),
+        ("....7..2.8.......6.1.2.5...9.54....8.........3....85.1...3.2.8.4.......9.7..6....", SolveStatus::Solved),
+        //Easter Monster, AI Escargot and Arto Inkala's 2012 puzzle need nested forcing nets
+        ("1.......2.9.4...5...6...7...5.9.3.......7.......85..4.7.....6...3...9.8...2.....1", SolveStatus::Stuck),
+        ("1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..", SolveStatus::Stuck),
+        ("8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..", SolveStatus::Stuck),
+    ];
+    for (board_string, status) in expected.iter() {
+        let board = Board::new(board_string).unwrap();
+        let solution = LogicalSolver::new().solve(&board);
+        assert_eq!(solution.status, *status, "{}", board_string);
+
+        let expected = solve_sudoku(&board).unwrap();
+        for step in solution.steps.iter() {
+            for placement in step.placements.iter() {
+                assert_eq!(expected.get(placement.cell.col, placement.cell.row), placement.value, "{}", step);
+            }
+            for elimination in step.eliminations.iter() {
+                assert_ne!(expected.get(elimination.cell.col, elimination.cell.row), elimination.value, "{}", step);
+            }
+        }
+    }
+}
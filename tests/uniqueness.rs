@@ -0,0 +1,83 @@
+extern crate sudoku_solver;
+
+use sudoku_solver::Board;
+use sudoku_solver::logical_solver::{LogicalSolver, SolveStatus};
+use sudoku_solver::sudoku_solver_backtrack;
+use sudoku_solver::generator::Generator;
+use sudoku_solver::sudoku_solver_dancinglinks::{count_solutions, has_unique_solution, is_minimal, redundant_clues};
+
+//Needs a Unique Rectangle Type 1 on top of the basic techniques
+const RECTANGLE: &str = "4...6..1...6.......1.2..43.....8.9..86......2....45..3...41.....7.......28....5..";
+//RECTANGLE without the 4 at r1c1, leaving it with 714 solutions
+const AMBIGUOUS: &str = "....6..1...6.......1.2..43.....8.9..86......2....45..3...41.....7.......28....5..";
+//RECTANGLE with a 2 at r1c2 that no solution has room for
+const UNSOLVABLE: &str = "42..6..1...6.......1.2..43.....8.9..86......2....45..3...41.....7.......28....5..";
+
+#[test]
+fn dancing_links_counts_agree_with_backtracker() {
+    let expected = [(RECTANGLE, 1), (AMBIGUOUS, 714), (UNSOLVABLE, 0)];
+    for &(board_string, count) in expected.iter() {
+        let board = Board::new(board_string).unwrap();
+        //Counting stops at the limit, and never goes past the actual number of solutions
+        for limit in [0, 1, 2, count + 1].iter() {
+            let capped = count.min(*limit);
+            assert_eq!(count_solutions(&board, *limit), capped, "{} {}", board_string, limit);
+            assert_eq!(sudoku_solver_backtrack::count_solutions(&board, *limit), capped, "{} {}", board_string, limit);
+        }
+
+        assert_eq!(has_unique_solution(&board), count == 1, "{}", board_string);
+        assert_eq!(sudoku_solver_backtrack::has_unique_solution(&board), count == 1, "{}", board_string);
+    }
+}
+
+
+#[test]
+fn uniqueness_techniques_need_the_option() {
+    let board = Board::new(RECTANGLE).unwrap();
+    let solution = LogicalSolver::new().assume_uniqueness(true).solve(&board);
+    assert_eq!(solution.status, SolveStatus::Solved);
+    assert!(solution.steps.iter().any(|step| step.technique.requires_uniqueness()));
+
+    let solution = LogicalSolver::new().solve(&board);
+    assert!(solution.steps.iter().all(|step| !step.technique.requires_uniqueness()));
+}
+
+#[test]
+fn uniqueness_techniques_skip_ambiguous_boards() {
+    let board = Board::new(AMBIGUOUS).unwrap();
+    assert!(count_solutions(&board, 2) > 1);
+
+    let solution = LogicalSolver::new().assume_uniqueness(true).solve(&board);
+    assert_eq!(solution.status, SolveStatus::Stuck);
+    assert!(solution.steps.iter().all(|step| !step.technique.requires_uniqueness()));
+}
+
+#[test]
+fn redundant_clues_of_a_minimal_puzzle() {
+    let puzzle = Generator::new(5).generate();
+    assert!(is_minimal(&puzzle.board));
+    assert!(redundant_clues(&puzzle.board).is_empty());
+
+    //A clue added from the solution can always go again, and other clues may become redundant
+    //along with it
+    let idx = puzzle.board.cells().iter().position(|&val| val == 0).unwrap();
+    let mut cells = puzzle.board.cells().to_vec();
+    cells[idx] = puzzle.solution.cells()[idx];
+    let board = Board::from_cells(cells);
+    assert!(!is_minimal(&board));
+
+    let redundant = redundant_clues(&board);
+    assert!(redundant.contains(&board.cell_at(idx)));
+    for cell in redundant.iter() {
+        let mut cells = board.cells().to_vec();
+        cells[board.index_of(*cell)] = 0;
+        assert!(has_unique_solution(&Board::from_cells(cells)), "{}", cell);
+    }
+}
+
+#[test]
+fn ambiguous_boards_are_not_minimal() {
+    let board = Board::new(AMBIGUOUS).unwrap();
+    assert!(!is_minimal(&board));
+    assert!(redundant_clues(&board).is_empty());
+}
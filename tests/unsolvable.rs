@@ -0,0 +1,56 @@
+extern crate sudoku_solver;
+
+use sudoku_solver::{Board, Unsolvable};
+use sudoku_solver::sudoku_solver_backtrack::Sudoku;
+use sudoku_solver::sudoku_solver_dancinglinks::{solve_sudoku, solve_sudoku_with_stats};
+
+//r1c9 can only be a 9 but column 9 already has a 9
+const NO_CANDIDATE: &str = "12345678.........9...............................................................";
+//r1c8 and r1c9 both need the 9 that is missing from row 1
+const SHARED_CANDIDATE: &str = "1234567....................................8...........................8.........";
+//SOLVABLE with a wrong but locally consistent 3 added at r1c2, so only the search can discover
+//the contradiction
+const DEEP_CONTRADICTION: &str = "230005709900070000070001050008200000067000520000008100040300080000060002609800003";
+const SOLVABLE: &str = "200005709900070000070001050008200000067000520000008100040300080000060002609800003";
+
+#[test]
+fn dancing_links_reports_contradictory_givens() {
+    for board_string in [NO_CANDIDATE, SHARED_CANDIDATE, DEEP_CONTRADICTION].iter() {
+        let board = Board::new(board_string).unwrap();
+        assert_eq!(solve_sudoku(&board), Err(Unsolvable), "{}", board_string);
+    }
+}
+
+#[test]
+fn backtracker_reports_contradictory_givens() {
+    for board_string in [NO_CANDIDATE, SHARED_CANDIDATE, DEEP_CONTRADICTION].iter() {
+        let sudoku = Sudoku::new(board_string).unwrap();
+        assert_eq!(sudoku.solve(), Err(Unsolvable), "{}", board_string);
+    }
+}
+
+#[test]
+fn solvers_agree_on_solvable_board() {
+    let board = Board::new(SOLVABLE).unwrap();
+    let solution = solve_sudoku(&board).unwrap();
+
+    assert!(solution.is_complete());
+    assert_eq!(Sudoku::new(SOLVABLE).unwrap().solve(), Ok(solution));
+}
+
+#[test]
+fn search_stats_come_with_the_same_result() {
+    for board_string in [SOLVABLE, DEEP_CONTRADICTION].iter() {
+        let board = Board::new(board_string).unwrap();
+        let (solution, stats) = solve_sudoku_with_stats(&board);
+
+        assert_eq!(solution, solve_sudoku(&board), "{}", board_string);
+        assert_eq!(stats.levels.len(), stats.max_depth + 1);
+        assert_eq!(stats.levels.iter().map(|level| level.nodes).sum::<usize>(), stats.nodes);
+    }
+
+    //Every cell of the contradiction is forced until the search runs into a dead end
+    let (_, stats) = solve_sudoku_with_stats(&Board::new(DEEP_CONTRADICTION).unwrap());
+    assert_eq!(stats.guesses, 0);
+    assert_eq!(stats.backtracks, stats.max_depth);
+}